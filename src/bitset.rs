@@ -159,8 +159,8 @@ where
     }
 
     /// Returns the number of elements in this set.
-    pub fn len(&self) -> u8 {
-        T::count_possibilities(self.0) as u8
+    pub fn len(&self) -> usize {
+        T::count_possibilities(self.0) as usize
     }
 
     /// Checks whether this set contains any element.
@@ -205,6 +205,25 @@ where
     }
 }
 
+impl Set<Digit> {
+    /// Construct a set of the given digits.
+    ///
+    /// ```
+    /// # use sudoku::bitset::Set;
+    /// # use sudoku::board::Digit;
+    /// let digits = Set::from_digits(&[1, 4, 7]);
+    /// assert_eq!(digits, Digit::new(1).as_set() | Digit::new(4) | Digit::new(7));
+    /// ```
+    ///
+    /// # Panic
+    /// Panics, if any digit is not in the range of `1..=9`.
+    pub fn from_digits(digits: &[u8]) -> Self {
+        digits
+            .iter()
+            .fold(Set::NONE, |set, &digit| set | Digit::new(digit))
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Trait for types that can be stored in a [`Set`]
@@ -5,6 +5,7 @@ mod cell_state;
 mod digit;
 mod grid_state;
 pub mod positions;
+mod puzzle;
 mod sudoku;
 
 pub(crate) use self::positions::*;
@@ -15,7 +16,9 @@ pub use self::{
     sudoku::Symmetry,
     digit::Digit,
     positions::Cell,
+    positions::{row_of, col_of, block_of},
     candidate::Candidate,
     cell_state::CellState,
     grid_state::GridState,
+    puzzle::Puzzle,
 };
@@ -30,7 +30,7 @@ impl Digit {
         Self::new_checked(idx + 1).unwrap()
     }
 
-    /// Returns an iterator over all digits.
+    /// Returns an iterator over all digits in ascending order, from 1 to 9.
     pub fn all() -> impl Iterator<Item = Self> {
         (1..10).map(Digit::new)
     }
@@ -37,7 +37,6 @@ impl std::fmt::Display for GridState {
         let stack_width = |stack_nr: usize| {
             column_widths[stack_nr * 3..stack_nr * 3 + 3]
                 .iter()
-                .map(|&x| x as usize)
                 .sum::<usize>()
                 + 6 // spaces in between cells and walls
         };
@@ -66,9 +65,9 @@ impl std::fmt::Display for GridState {
                 self.0[base_idx],
                 self.0[base_idx + 1],
                 self.0[base_idx + 2],
-                width1 = column_widths[base_col] as usize,
-                width2 = column_widths[base_col + 1] as usize,
-                width3 = column_widths[base_col + 2] as usize,
+                width1 = column_widths[base_col],
+                width2 = column_widths[base_col + 1],
+                width3 = column_widths[base_col + 2],
             )
         };
 
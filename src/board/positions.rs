@@ -4,9 +4,10 @@
 #![allow(unused, missing_docs)]
 
 use crate::bitset::{Set, SetElement};
-use crate::board::Digit;
+use crate::board::{Digit, Symmetry};
 use crate::consts::*;
 use crate::helper::Unsolvable;
+use std::fmt;
 use std::num::NonZeroU8;
 
 #[inline(always)]
@@ -22,6 +23,54 @@ pub(crate) fn block(cell: u8) -> u8 {
     BLOCK[cell as usize]
 }
 
+/// Returns the row of the cell with the given index.
+///
+/// Cells are numbered `0..81` from left to right, top to bottom, the same order as in the
+/// [`Sudoku`](crate::Sudoku) byte and line formats. Rows are numbered `0..9` from top to bottom.
+///
+/// # Panics
+/// panics if `cell >= 81`
+pub fn row_of(cell: usize) -> usize {
+    assert!(cell < N_CELLS);
+    row(cell as u8) as usize
+}
+
+/// Returns the column of the cell with the given index.
+///
+/// Cells are numbered `0..81` from left to right, top to bottom, the same order as in the
+/// [`Sudoku`](crate::Sudoku) byte and line formats. Columns are numbered `0..9` from left to right.
+///
+/// # Panics
+/// panics if `cell >= 81`
+pub fn col_of(cell: usize) -> usize {
+    assert!(cell < N_CELLS);
+    col(cell as u8) as usize
+}
+
+/// Returns the block of the cell with the given index.
+///
+/// Cells are numbered `0..81` from left to right, top to bottom, the same order as in the
+/// [`Sudoku`](crate::Sudoku) byte and line formats. Blocks are numbered `0..9` from left to right, top to bottom.
+///
+/// # Panics
+/// panics if `cell >= 81`
+///
+/// # Example
+///
+/// ```
+/// use sudoku::board::{block_of, col_of, row_of};
+///
+/// // the cell in the middle of the bottom row
+/// let cell = 76;
+/// assert_eq!(row_of(cell), 8);
+/// assert_eq!(col_of(cell), 4);
+/// assert_eq!(block_of(cell), 7);
+/// ```
+pub fn block_of(cell: usize) -> usize {
+    assert!(cell < N_CELLS);
+    block(cell as u8) as usize
+}
+
 fn band(cell: u8) -> u8 {
     cell / 27
 }
@@ -163,6 +212,94 @@ static HOUSE_NEIGHBORS_OF_CELL: [[u8; 20]; 81] = [
     [8, 17, 26, 35, 44, 53, 60, 61, 62, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79],
 ];
 
+// set of cells that share a row, col or block for a given cell
+// bit n corresponds to cell n, so each group of 3 octal digits is a row
+// from the last row on the left to the first row on the right
+#[rustfmt::skip]
+static PEER_SETS: [u128; 81] = [
+    0o001_001_001_001_001_001_007_007_776,
+    0o002_002_002_002_002_002_007_007_775,
+    0o004_004_004_004_004_004_007_007_773,
+    0o010_010_010_010_010_010_070_070_767,
+    0o020_020_020_020_020_020_070_070_757,
+    0o040_040_040_040_040_040_070_070_737,
+    0o100_100_100_100_100_100_700_700_677,
+    0o200_200_200_200_200_200_700_700_577,
+    0o400_400_400_400_400_400_700_700_377,
+    0o001_001_001_001_001_001_007_776_007,
+    0o002_002_002_002_002_002_007_775_007,
+    0o004_004_004_004_004_004_007_773_007,
+    0o010_010_010_010_010_010_070_767_070,
+    0o020_020_020_020_020_020_070_757_070,
+    0o040_040_040_040_040_040_070_737_070,
+    0o100_100_100_100_100_100_700_677_700,
+    0o200_200_200_200_200_200_700_577_700,
+    0o400_400_400_400_400_400_700_377_700,
+    0o001_001_001_001_001_001_776_007_007,
+    0o002_002_002_002_002_002_775_007_007,
+    0o004_004_004_004_004_004_773_007_007,
+    0o010_010_010_010_010_010_767_070_070,
+    0o020_020_020_020_020_020_757_070_070,
+    0o040_040_040_040_040_040_737_070_070,
+    0o100_100_100_100_100_100_677_700_700,
+    0o200_200_200_200_200_200_577_700_700,
+    0o400_400_400_400_400_400_377_700_700,
+    0o001_001_001_007_007_776_001_001_001,
+    0o002_002_002_007_007_775_002_002_002,
+    0o004_004_004_007_007_773_004_004_004,
+    0o010_010_010_070_070_767_010_010_010,
+    0o020_020_020_070_070_757_020_020_020,
+    0o040_040_040_070_070_737_040_040_040,
+    0o100_100_100_700_700_677_100_100_100,
+    0o200_200_200_700_700_577_200_200_200,
+    0o400_400_400_700_700_377_400_400_400,
+    0o001_001_001_007_776_007_001_001_001,
+    0o002_002_002_007_775_007_002_002_002,
+    0o004_004_004_007_773_007_004_004_004,
+    0o010_010_010_070_767_070_010_010_010,
+    0o020_020_020_070_757_070_020_020_020,
+    0o040_040_040_070_737_070_040_040_040,
+    0o100_100_100_700_677_700_100_100_100,
+    0o200_200_200_700_577_700_200_200_200,
+    0o400_400_400_700_377_700_400_400_400,
+    0o001_001_001_776_007_007_001_001_001,
+    0o002_002_002_775_007_007_002_002_002,
+    0o004_004_004_773_007_007_004_004_004,
+    0o010_010_010_767_070_070_010_010_010,
+    0o020_020_020_757_070_070_020_020_020,
+    0o040_040_040_737_070_070_040_040_040,
+    0o100_100_100_677_700_700_100_100_100,
+    0o200_200_200_577_700_700_200_200_200,
+    0o400_400_400_377_700_700_400_400_400,
+    0o007_007_776_001_001_001_001_001_001,
+    0o007_007_775_002_002_002_002_002_002,
+    0o007_007_773_004_004_004_004_004_004,
+    0o070_070_767_010_010_010_010_010_010,
+    0o070_070_757_020_020_020_020_020_020,
+    0o070_070_737_040_040_040_040_040_040,
+    0o700_700_677_100_100_100_100_100_100,
+    0o700_700_577_200_200_200_200_200_200,
+    0o700_700_377_400_400_400_400_400_400,
+    0o007_776_007_001_001_001_001_001_001,
+    0o007_775_007_002_002_002_002_002_002,
+    0o007_773_007_004_004_004_004_004_004,
+    0o070_767_070_010_010_010_010_010_010,
+    0o070_757_070_020_020_020_020_020_020,
+    0o070_737_070_040_040_040_040_040_040,
+    0o700_677_700_100_100_100_100_100_100,
+    0o700_577_700_200_200_200_200_200_200,
+    0o700_377_700_400_400_400_400_400_400,
+    0o776_007_007_001_001_001_001_001_001,
+    0o775_007_007_002_002_002_002_002_002,
+    0o773_007_007_004_004_004_004_004_004,
+    0o767_070_070_010_010_010_010_010_010,
+    0o757_070_070_020_020_020_020_020_020,
+    0o737_070_070_040_040_040_040_040_040,
+    0o677_700_700_100_100_100_100_100_100,
+    0o577_700_700_200_200_200_200_200_200,
+    0o377_700_700_400_400_400_400_400_400,
+];
+
 macro_rules! define_types(
     ($( $name:ident : $limit:expr ),* $(,)*) => {
         $(
@@ -203,7 +340,7 @@ macro_rules! define_types(
                 self.0 as _
             }
 
-            /// Iterator over all positions of this type.
+            /// Iterator over all positions of this type, in ascending order of their numbers, starting at `0`.
             pub fn all() -> impl Iterator<Item = Self> {
                 (0..$limit).map(Self::new)
             }
@@ -212,26 +349,49 @@ macro_rules! define_types(
 );
 
 /// One of the 81 cells of the sudoku
+///
+/// Cells are numbered `0..81` from left to right, top to bottom.
+///
+/// ```
+/// # use sudoku::board::Cell;
+/// let cells: Vec<_> = Cell::all().collect();
+/// assert_eq!(cells.len(), 81);
+/// for (index, cell) in cells.into_iter().enumerate() {
+///     assert_eq!(cell.as_index(), index);
+///     assert_eq!(cell.row().get() as usize, index / 9);
+///     assert_eq!(cell.col().get() as usize, index % 9);
+/// }
+/// ```
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Cell(u8);
 
 /// Set of 9 cells in a horizontal line
+///
+/// Rows are numbered `0..9` from top to bottom.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Row(u8);
 
 /// Set of 9 cells in a vertical line
+///
+/// Columns are numbered `0..9` from left to right.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Col(u8);
 
 /// Set of 9 cells in a 3x3 box shape
+///
+/// Blocks are numbered `0..9` from left to right, top to bottom.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Block(u8);
 
 /// A [`Row`] or [`Col`]
+///
+/// Lines `0..9` are the rows, lines `9..18` the columns.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Line(u8);
 
 /// A [`Row`], [`Col`] or [`Block`]
+///
+/// Houses `0..9` are the rows, houses `9..18` the columns and houses `18..27` the blocks.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct House(u8);
 
@@ -256,6 +416,8 @@ pub struct Band(u8);
 pub struct Stack(u8);
 
 /// A [`Band`] or [`Stack`]
+///
+/// Chutes `0..3` are the bands, chutes `3..6` the stacks.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Hash)]
 pub struct Chute(u8);
 
@@ -300,6 +462,18 @@ pub enum HouseType {
     Block(Block),
 }
 
+/// Formats the house for humans as `row 1` to `row 9`, `column 1` to `column 9`
+/// or `box 1` to `box 9`. Unlike the indices, the numbers start at 1.
+impl fmt::Display for HouseType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HouseType::Row(row) => write!(f, "row {}", row.get() + 1),
+            HouseType::Col(col) => write!(f, "column {}", col.get() + 1),
+            HouseType::Block(block) => write!(f, "box {}", block.get() + 1),
+        }
+    }
+}
+
 impl House {
     pub(crate) const ALL_ROWS: Set<House> = Set(0o000_000_777);
     pub(crate) const ALL_COLS: Set<House> = Set(0o000_777_000);
@@ -501,18 +675,55 @@ define_conversion_shortcuts!(
     */
 );
 
+/// The positions of a cell inside its houses. They are the inverse of [`CellAt::cell_at`]
+/// for the respective house.
+///
+/// ```
+/// # use sudoku::board::Cell;
+/// # use sudoku::board::positions::{CellAt, House};
+/// let cell = Cell::new(22); // row 3, column 5
+/// assert_eq!(cell.row_pos().get(), 4);
+/// assert_eq!(cell.col_pos().get(), 2);
+/// assert_eq!(cell.block_pos().get(), 7);
+///
+/// assert_eq!(House::from(cell.row()).cell_at(cell.row_pos()), cell);
+/// assert_eq!(House::from(cell.col()).cell_at(cell.col_pos()), cell);
+/// assert_eq!(House::from(cell.block()).cell_at(cell.block_pos()), cell);
+/// ```
 impl Cell {
-    pub(crate) fn row_pos(self) -> Position<House> {
+    /// Returns the position of this cell in its row, i.e. its column index.
+    pub fn row_pos(self) -> Position<House> {
         Position::<Row>::from(self).into()
     }
 
-    pub(crate) fn col_pos(self) -> Position<House> {
+    /// Returns the position of this cell in its column, i.e. its row index.
+    pub fn col_pos(self) -> Position<House> {
         Position::<Col>::from(self).into()
     }
 
-    pub(crate) fn block_pos(self) -> Position<House> {
+    /// Returns the position of this cell in its block, counting from left to right, then top to bottom.
+    pub fn block_pos(self) -> Position<House> {
         Position::<Block>::from(self).into()
     }
+
+    /// Returns the index of the block this cell belongs to. Blocks are numbered `0..9` from left to right,
+    /// then top to bottom.
+    ///
+    /// ```
+    /// # use sudoku::board::Cell;
+    /// let cell = Cell::new(22); // row 3, column 5
+    /// assert_eq!(cell.block_index(), 1);
+    /// assert_eq!(cell.index_in_block(), 7);
+    /// ```
+    pub fn block_index(self) -> u8 {
+        block(self.get())
+    }
+
+    /// Returns the position of this cell inside its block in `0..9`, counting from left to right,
+    /// then top to bottom. This is the same as [`block_pos`](Self::block_pos) as a plain number.
+    pub fn index_in_block(self) -> u8 {
+        row(self.get()) % 3 * 3 + col(self.get()) % 3
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////////////
@@ -533,12 +744,32 @@ impl Cell {
             .map(Cell::new)
     }
 
-    /// Returns a set of the 20 cells that share a house with this one.
+    /// Returns the set of the 20 cells that share a house with this one, its peers.
+    /// The cells that see both `a` and `b` are `a.peer_set() & b.peer_set()`.
     #[inline(always)]
-    #[rustfmt::skip]
-    pub(crate) fn neighbors_set(self) -> Set<Cell> {
-        (self.row().cells() | self.col().cells() | self.block().cells())
-            ^ self
+    pub fn peer_set(self) -> Set<Cell> {
+        Set(PEER_SETS[self.as_index()])
+    }
+
+    /// Returns the cells that `symmetry` maps this cell onto, including itself, in ascending order.
+    /// To uphold the symmetry, these cells must either all be filled or all be empty.
+    ///
+    /// ```
+    /// use sudoku::board::{Cell, Symmetry};
+    ///
+    /// // 180° rotation maps the cells onto each other, except for the center
+    /// assert_eq!(Cell::new(1).orbit(Symmetry::HalfRotation), vec![Cell::new(1), Cell::new(79)]);
+    /// assert_eq!(Cell::new(40).orbit(Symmetry::HalfRotation), vec![Cell::new(40)]);
+    /// ```
+    pub fn orbit(self, symmetry: Symmetry) -> Vec<Cell> {
+        let mut cells = symmetry
+            .corresponding_cells(self.as_index())
+            .into_iter()
+            .map(|cell| Cell::new(cell as u8))
+            .collect::<Vec<_>>();
+        cells.sort();
+        cells.dedup();
+        cells
     }
 }
 
@@ -581,6 +812,10 @@ impl MiniLine {
 ///////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Trait for cell groupings that have all of their cells enumerated
+///
+/// Positions in rows and columns are the column and row indices of the cells respectively.
+/// Positions in blocks count from left to right, then top to bottom. For the inverse, see
+/// [`Cell::row_pos`], [`Cell::col_pos`] and [`Cell::block_pos`].
 pub trait CellAt: Sized {
     /// Return the cell at the given position in this cell grouping
     fn cell_at(self, pos: Position<Self>) -> Cell;
@@ -718,13 +953,14 @@ impl_from!(
     Position<Block>, Set<Position<House>>, |pos| { Position::<House>::from(pos).as_set() },
 );*/
 
-// TODO: generalize
-impl Set<Position<House>> {
-    pub fn as_line_set(self) -> Set<Position<Line>> {
-        debug_assert!(self.0 <= Set::<Position<Line>>::ALL.0);
-        Set::from_bits(self.0)
-    }
-}
+// Positions in lines are positions in houses and vice versa, if the house is a line.
+// For blocks, the conversion to line positions keeps the numbering, but has no geometric meaning.
+#[rustfmt::skip]
+impl_from_raw!(
+    Position<House>, Position<Line>, |pos| { Position::new(pos.0) },
+    Set<Position<Line>>, Set<Position<House>>, |set| { Set(set.0) },
+    Set<Position<House>>, Set<Position<Line>>, |set| { Set(set.0) },
+);
 
 impl<T: SetElement> From<T> for Set<T> {
     fn from(element: T) -> Self {
@@ -854,6 +1090,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn line_house_position_roundtrip() {
+        for pos in (0..9).map(Position::<Line>::new) {
+            let house_pos = Position::<House>::from(pos);
+            assert_eq!(house_pos.get(), pos.get());
+            assert_eq!(Position::<Line>::from(house_pos), pos);
+        }
+
+        for bits in 0..=0o777 {
+            let set = Set::<Position<Line>>::from_bits(bits);
+            let house_set = Set::<Position<House>>::from(set);
+            assert!(house_set
+                .into_iter()
+                .map(Position::<Line>::from)
+                .eq(set.into_iter()));
+            assert_eq!(Set::<Position<Line>>::from(house_set), set);
+        }
+    }
+
+    #[test]
+    fn house_type_display() {
+        assert_eq!(House::new(0).categorize().to_string(), "row 1");
+        assert_eq!(House::new(17).categorize().to_string(), "column 9");
+        assert_eq!(House::new(22).categorize().to_string(), "box 5");
+    }
+
+    #[test]
+    fn peer_set() {
+        for cell in Cell::all() {
+            let peers = cell
+                .neighbors()
+                .into_iter()
+                .fold(Set::NONE, std::ops::BitOr::bitor);
+            assert_eq!(cell.peer_set(), peers);
+            assert_eq!(
+                cell.peer_set(),
+                (cell.row().cells() | cell.col().cells() | cell.block().cells()) ^ cell
+            );
+        }
+    }
+
     /*
     #[test]
     fn block_cells() {
@@ -895,4 +1172,24 @@ mod test {
             assert!(iter1.eq(iter2));
         }
     }
+
+    #[test]
+    fn raw_index_houses() {
+        for cell in Cell::all() {
+            let idx = cell.as_index();
+            assert_eq!(row_of(idx), cell.row().as_index());
+            assert_eq!(col_of(idx), cell.col().as_index());
+            assert_eq!(block_of(idx), cell.block().as_index());
+        }
+    }
+
+    #[test]
+    fn block_relative_index() {
+        for cell in Cell::all() {
+            let (block, pos) = (cell.block_index(), cell.index_in_block());
+            assert_eq!(block, cell.block().get());
+            assert_eq!(pos, cell.block_pos().get());
+            assert_eq!(Block::new(block).cell_at(Position::new(pos)), cell);
+        }
+    }
 }
@@ -0,0 +1,62 @@
+use crate::board::{Candidate, Cell};
+use crate::Sudoku;
+
+/// A sudoku that remembers which cells are givens, so they can be told apart from solved cells,
+/// e.g. to style them differently.
+///
+/// ```
+/// # use sudoku::board::{Cell, Puzzle};
+/// # use sudoku::Sudoku;
+/// let sudoku = Sudoku::from_str_line(
+///     "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+/// ).unwrap();
+/// let mut puzzle = Puzzle::from_givens(sudoku);
+/// assert!(puzzle.solve());
+/// assert!(puzzle.grid().is_solved());
+/// assert!(puzzle.is_given(Cell::new(3)));
+/// assert!(!puzzle.is_given(Cell::new(0)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    givens: Sudoku,
+    grid: Sudoku,
+}
+
+impl Puzzle {
+    /// Creates a puzzle whose filled cells are all givens.
+    pub fn from_givens(givens: Sudoku) -> Puzzle {
+        Puzzle { givens, grid: givens }
+    }
+
+    /// Returns the givens the puzzle was created from.
+    pub fn givens(&self) -> Sudoku {
+        self.givens
+    }
+
+    /// Returns the current grid, including the givens and all digits filled in by solving.
+    pub fn grid(&self) -> Sudoku {
+        self.grid
+    }
+
+    /// Checks whether `cell` contains a given.
+    pub fn is_given(&self, cell: Cell) -> bool {
+        self.givens.0[cell.as_index()] != 0
+    }
+
+    /// Returns an iterator over the givens and their cells.
+    pub fn givens_iter(&self) -> impl Iterator<Item = Candidate> {
+        self.givens.clues()
+    }
+
+    /// Fills the remaining cells with the solution of the givens. Returns `false` and leaves the grid
+    /// unchanged, if there is no unique solution.
+    pub fn solve(&mut self) -> bool {
+        match self.givens.solution() {
+            Some(solution) => {
+                self.grid = solution;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -1,22 +1,30 @@
-use rand::Rng;
+use rand::{prng::ChaChaRng, Rng, SeedableRng};
 
+use crate::bitset::Set;
+use crate::board::{Candidate, Cell, Digit, House};
 use crate::consts::*;
 use crate::generator::SudokuGenerator;
-use crate::parse_errors::{BlockParseError, InvalidEntry, LineParseError, NotEnoughRows};
-use crate::solver::SudokuSolver;
+use crate::parse_errors::{
+    BlockParseError, FromBytesError, InvalidEntry, LineParseError, NotEnoughRows, ParseError,
+};
+use crate::solver::{GridError, SolveStats, SudokuSolver, Timeout, Uniqueness};
+use crate::strategy::{Difficulty, StrategySolver, Tier};
 
 #[cfg(feature = "serde")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
-    cmp, fmt, hash, iter,
+    cmp,
+    convert::TryFrom,
+    fmt, hash, io, iter,
     ops::{self, Deref},
     slice, str,
+    time::Instant,
 };
 
 /// The main structure exposing all the functionality of the library
 ///
 /// `Sudoku`s can generated, constructed from arrays or parsed from `&str`s
-/// in either the line or block format.
+/// in either the line or block format, e.g. with [`str::parse`](#impl-FromStr).
 #[derive(Copy, Clone)]
 pub struct Sudoku(pub(crate) [u8; N_CELLS]);
 
@@ -51,9 +59,7 @@ impl de::Visitor<'_> for ByteSudoku {
     where
         E: de::Error,
     {
-        // FIXME: return proper error
-        Sudoku::from_bytes_slice(v)
-            .map_err(|_| E::custom("byte array has incorrect length or contains numbers not from 0 to 9"))
+        Sudoku::from_bytes_slice(v).map_err(E::custom)
     }
 }
 
@@ -86,6 +92,13 @@ impl<'de> Deserialize<'de> for Sudoku {
     }
 }
 
+/// The empty sudoku, see [`Sudoku::empty`].
+impl Default for Sudoku {
+    fn default() -> Self {
+        Sudoku::empty()
+    }
+}
+
 impl PartialEq for Sudoku {
     fn eq(&self, other: &Sudoku) -> bool {
         self.0[..] == other.0[..]
@@ -158,7 +171,7 @@ pub enum Symmetry {
 
 impl Symmetry {
     // For a given cell, returns all cells that need to be either all filled or all empty to uphold the symmetry
-    fn corresponding_cells(self, cell: usize) -> Vec<usize> {
+    pub(crate) fn corresponding_cells(self, cell: usize) -> Vec<usize> {
         let row = cell / 9;
         let col = cell % 9;
         let mirr = |line| 8 - line; // mirror along the axis orthogonal to `line`
@@ -205,7 +218,7 @@ impl Symmetry {
 impl Sudoku {
     /// Generate a random, solved sudoku
     pub fn generate_solved() -> Self {
-        SudokuGenerator::generate_solved()
+        SudokuGenerator::generate_solved(&mut rand::thread_rng())
     }
 
     /// Generate a random, uniquely solvable sudoku with 180° rotational symmetry.
@@ -242,7 +255,32 @@ impl Sudoku {
     /// Most puzzles generated by this from solved sudokus are easy.
     ///
     /// If the source `sudoku` is invalid or has multiple solutions, it will be returned as is.
-    pub fn generate_with_symmetry_from(mut sudoku: Sudoku, symmetry: Symmetry) -> Self {
+    /// In particular, the [empty](Self::empty) grid is never a starting point for removing clues.
+    /// The other generation functions always start from a randomly [solved](Self::generate_solved) grid.
+    pub fn generate_with_symmetry_from(sudoku: Sudoku, symmetry: Symmetry) -> Self {
+        Sudoku::generate_with_symmetry_from_rng(sudoku, symmetry, &mut rand::thread_rng())
+    }
+
+    /// Generate a random, uniquely solvable sudoku whose difficulty, as graded by the [`StrategySolver`],
+    /// is `target`. The same `seed` always produces the same sudoku.
+    ///
+    /// Random sudokus are generated and graded until one matches the target difficulty.
+    /// Returns `None`, if none was found within a fixed number of attempts. Most generated sudokus
+    /// are easy or medium, so harder targets need many more attempts and fail more often.
+    /// Sudokus that can not be solved with the available strategies are never returned.
+    pub fn generate_with_difficulty(target: Difficulty, seed: u64) -> Option<Self> {
+        const MAX_ATTEMPTS: usize = 1000;
+        let rng = &mut ChaChaRng::seed_from_u64(seed);
+
+        (0..MAX_ATTEMPTS)
+            .map(|_| {
+                let solved = SudokuGenerator::generate_solved(rng);
+                Sudoku::generate_with_symmetry_from_rng(solved, Symmetry::None, rng)
+            })
+            .find(|&sudoku| sudoku.grade() == Some(target))
+    }
+
+    fn generate_with_symmetry_from_rng(mut sudoku: Sudoku, symmetry: Symmetry, rng: &mut impl Rng) -> Self {
         // this function is following
         // the approach outlined here: https://stackoverflow.com/a/7280517
         //
@@ -256,57 +294,123 @@ impl Sudoku {
             .iter_mut()
             .enumerate()
             .for_each(|(cell, place)| *place = cell);
-        rand::thread_rng().shuffle(&mut cell_order);
+        rng.shuffle(&mut cell_order);
 
         // With symmetries, many cells are equivalent.
         // If we've already visited one cell in a symmetry class, we can skip ahead
         // when encountering one of the other ones.
         let mut cell_visited = [false; 81];
 
+        // The solver is kept in sync with the remaining clues, so that every trial
+        // only has to undo the removed clues instead of inserting all the others again.
+        let mut solver = match SudokuSolver::from_sudoku(sudoku) {
+            Ok(solver) => solver,
+            Err(_) => return sudoku,
+        };
+
         // remove cell content if possible without destroying uniqueness of solution
         for &cell in &cell_order[..] {
-            let cells = symmetry.corresponding_cells(cell);
-            if cell_visited[cells[0]] {
+            let cells = Cell::new(cell as u8).orbit(symmetry);
+            if cell_visited[cells[0].as_index()] {
                 continue;
             }
             let mut sudoku_tmp = sudoku;
+            let mut solver_tmp = solver;
             for cell in cells {
-                cell_visited[cell] = true;
-                sudoku_tmp.0[cell] = 0;
+                cell_visited[cell.as_index()] = true;
+                let num = sudoku_tmp.0[cell.as_index()];
+                if num != 0 {
+                    sudoku_tmp.0[cell.as_index()] = 0;
+                    solver_tmp.remove_clue(&sudoku_tmp, cell.get(), num);
+                }
             }
-            if sudoku_tmp.is_uniquely_solvable() {
+            if solver_tmp.solutions_count_up_to(2) == 1 {
                 sudoku = sudoku_tmp;
+                solver = solver_tmp;
             }
         }
 
         sudoku
     }
 
+    /// Creates a sudoku without any clues. Equal to `Sudoku::default()`.
+    pub fn empty() -> Self {
+        Sudoku([0; N_CELLS])
+    }
+
     /// Creates a sudoku from a byte slice.
     /// All numbers must be below 10. Empty cells are denoted by 0, clues by the numbers 1-9.
     /// The slice must be of length 81.
-    pub fn from_bytes_slice(bytes: &[u8]) -> Result<Sudoku, ()> {
+    ///
+    /// # Errors
+    /// - [`WrongLength`](FromBytesError::WrongLength), if the slice isn't of length 81.
+    /// - [`InvalidNumber`](FromBytesError::InvalidNumber) with the first cell containing a number above 9.
+    pub fn from_bytes_slice(bytes: &[u8]) -> Result<Sudoku, FromBytesError> {
         if bytes.len() != N_CELLS {
-            return Err(());
-        }
-        let mut sudoku = Sudoku([0; N_CELLS]);
-
-        match bytes.iter().all(|&byte| byte <= 9) {
-            true => {
-                sudoku.0.copy_from_slice(bytes);
-                Ok(sudoku)
-            }
-            false => Err(()),
+            return Err(FromBytesError::WrongLength(bytes.len()));
         }
+        let mut grid = [0; N_CELLS];
+        grid.copy_from_slice(bytes);
+        Sudoku::from_bytes(grid)
     }
 
     /// Creates a sudoku from a byte array.
     /// All numbers must be below 10. Empty cells are denoted by 0, clues by the numbers 1-9.
-    pub fn from_bytes(bytes: [u8; N_CELLS]) -> Result<Sudoku, ()> {
+    ///
+    /// This is the inverse of [`to_bytes`](Self::to_bytes) and a compact alternative to the string formats
+    /// for storing sudokus.
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// # use sudoku::parse_errors::FromBytesError;
+    /// let mut bytes = [0; 81];
+    /// bytes[10] = 5;
+    /// let sudoku = Sudoku::from_bytes(bytes).unwrap();
+    /// assert_eq!(sudoku.to_bytes(), bytes);
+    ///
+    /// bytes[20] = 10;
+    /// assert_eq!(
+    ///     Sudoku::from_bytes(bytes),
+    ///     Err(FromBytesError::InvalidNumber { cell: 20, number: 10 }),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// [`InvalidNumber`](FromBytesError::InvalidNumber) with the first cell containing a number above 9.
+    pub fn from_bytes(bytes: [u8; N_CELLS]) -> Result<Sudoku, FromBytesError> {
+        // check all bytes at once and only search for the invalid one on failure
         match bytes.iter().fold(true, |valid, &byte| valid & (byte <= 9)) {
             true => Ok(Sudoku(bytes)),
-            false => Err(()),
+            false => {
+                let cell = bytes.iter().position(|&byte| byte > 9).unwrap();
+                Err(FromBytesError::InvalidNumber {
+                    cell: cell as u8,
+                    number: bytes[cell],
+                })
+            }
+        }
+    }
+
+    /// Creates a sudoku from a 2D array in row-major order, i.e. `grid[row][col]`.
+    /// All numbers must be below 10. Empty cells are denoted by 0, clues by the numbers 1-9.
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let mut grid = [[0; 9]; 9];
+    /// grid[0][8] = 7; // top right cell
+    /// let sudoku = Sudoku::from_2d(grid).unwrap();
+    /// assert_eq!(sudoku.to_bytes()[8], 7);
+    /// assert_eq!(sudoku.to_2d(), grid);
+    /// ```
+    ///
+    /// # Errors
+    /// Same as [`from_bytes`](Self::from_bytes). The cell is counted in row-major order.
+    pub fn from_2d(grid: [[u8; 9]; 9]) -> Result<Sudoku, FromBytesError> {
+        let mut bytes = [0; N_CELLS];
+        for (row, row_bytes) in grid.iter().zip(bytes.chunks_mut(9)) {
+            row_bytes.copy_from_slice(row);
         }
+        Sudoku::from_bytes(bytes)
     }
 
     /// Reads a sudoku in the line format.
@@ -325,7 +429,17 @@ impl Sudoku {
     /// ..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3.. optional comment
     /// ```
     ///
-    /// Stops parsing after the first sudoku
+    /// Stops parsing after the first sudoku. Every grid is read back unchanged from
+    /// [`to_str_line`](Self::to_str_line), even if it isn't a valid sudoku.
+    ///
+    /// # Errors
+    /// - [`InvalidEntry`](LineParseError::InvalidEntry), if one of the first 81 characters is neither
+    ///   a digit, an empty cell nor a delimiter. It contains the index of the cell and the character.
+    /// - [`NotEnoughCells`](LineParseError::NotEnoughCells) with the number of cells read, if the input
+    ///   ends or a delimiter follows before 81 cells were read.
+    /// - [`TooManyCells`](LineParseError::TooManyCells), if the 82nd character is a cell.
+    /// - [`MissingCommentDelimiter`](LineParseError::MissingCommentDelimiter), if the 82nd character is
+    ///   neither a cell nor a delimiter.
     pub fn from_str_line(s: &str) -> Result<Sudoku, LineParseError> {
         let chars = s.as_bytes();
         if let Ok(sudoku) = Sudoku::_from_str_line_fast_path(chars) {
@@ -338,8 +452,8 @@ impl Sudoku {
             match ch {
                 b'_' | b'.' => *cell = 0,
                 b'0'..=b'9' => *cell = ch - b'0',
-                // space ends sudoku before grid is filled
-                b' ' | b'\t' => return Err(LineParseError::NotEnoughCells(i)),
+                // delimiter ends sudoku before grid is filled
+                b' ' | b'\t' | b'\r' | b'\n' | b';' | b',' => return Err(LineParseError::NotEnoughCells(i)),
                 _ => {
                     return Err(LineParseError::InvalidEntry(InvalidEntry {
                         cell: i,
@@ -399,11 +513,33 @@ impl Sudoku {
         });
 
         match valid_ending {
-            true => Sudoku::from_bytes(grid),
+            true => Sudoku::from_bytes(grid).map_err(|_| ()),
             false => Err(()),
         }
     }
 
+    /// Reads a sudoku in the line format, followed by its [checksum](Self::checksum) as 4 hexadecimal digits,
+    /// as written by [`to_str_line_checked`](Self::to_str_line_checked). The checksum must be the last
+    /// entry of the line and separated like a comment. Other comments may be placed before it.
+    ///
+    /// Returns `LineParseError::ChecksumMismatch`, if the checksum is missing or doesn't match the sudoku.
+    pub fn from_str_line_checked(s: &str) -> Result<Sudoku, LineParseError> {
+        let sudoku = Sudoku::from_str_line(s)?;
+        let is_delimiter = |ch: char| " \t\r\n;,".contains(ch);
+        let checksum = s
+            .trim_end_matches(is_delimiter)
+            .rsplit(is_delimiter)
+            .next()
+            .unwrap_or("");
+        if checksum.len() != 4 || !checksum.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(LineParseError::ChecksumMismatch);
+        }
+        match u16::from_str_radix(checksum, 16) {
+            Ok(checksum) if checksum == sudoku.checksum() => Ok(sudoku),
+            _ => Err(LineParseError::ChecksumMismatch),
+        }
+    }
+
     /// Reads a sudoku in the block format with or without field delimiters
     ///
     /// Digits must be in range of 1-9.
@@ -541,16 +677,145 @@ impl Sudoku {
             n_line_sud += 1;
         }
         if n_line_sud != 9 {
-            return Err(BlockParseError::NotEnoughRows(n_line_sud + 1)); // number of rows = index of last + 1
+            return Err(BlockParseError::NotEnoughRows(n_line_sud));
         }
         Ok(Sudoku(grid))
     }
 
+    /// Reads exactly one sudoku in the block format from `reader`, as accepted by
+    /// [`from_str_block`](Self::from_str_block).
+    ///
+    /// Blank lines before the sudoku are skipped. Only the lines of the sudoku itself are consumed,
+    /// i.e. 9 lines or 11 with field delimiters, so that the reader is positioned at the next sudoku.
+    /// This allows iterating over concatenated block sudokus:
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let input = "\
+    /// __3_2_6__
+    /// 9__3_5__1
+    /// __18_64__
+    /// __81_29__
+    /// 7_______8
+    /// __67_82__
+    /// __26_95__
+    /// 8__2_3__9
+    /// __5_1_3__
+    ///
+    /// 4__|___|8_5
+    /// _3_|___|___
+    /// ___|7__|___
+    /// ---+---+---
+    /// _2_|___|_6_
+    /// ___|_8_|4__
+    /// ___|_1_|___
+    /// ---+---+---
+    /// ___|6_3|_7_
+    /// 5__|2__|___
+    /// 1_4|___|___
+    /// ";
+    ///
+    /// let mut reader = input.as_bytes();
+    /// let mut sudokus = vec![];
+    /// while let Some(sudoku) = Sudoku::from_reader_block(&mut reader)? {
+    ///     sudokus.push(sudoku.unwrap());
+    /// }
+    /// assert_eq!(sudokus.len(), 2);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// Returns `Ok(None)`, if the reader contains no further sudoku.
+    /// If the reader ends in the middle of a sudoku, `BlockParseError::NotEnoughRows` is returned.
+    pub fn from_reader_block<R: io::BufRead>(
+        reader: &mut R,
+    ) -> io::Result<Option<Result<Sudoku, BlockParseError>>> {
+        let first_line = match read_nonblank_line(reader)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let block = read_block(reader, first_line)?;
+        Ok(Some(Sudoku::from_str_block(&block)))
+    }
+
+    /// Reads exactly one sudoku from `reader` in either the line or the block format,
+    /// as accepted by [`from_str_line`](Self::from_str_line) and [`from_str_block`](Self::from_str_block).
+    ///
+    /// The format is detected from the first non-blank line. If it is longer than a row of the block format,
+    /// it is parsed as a sudoku in the line format, otherwise it is the first row of a block.
+    /// As with [`from_reader_block`](Self::from_reader_block), blank lines before the sudoku are skipped
+    /// and only the lines of the sudoku itself are consumed, so files mixing both formats can be read:
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let input = "\
+    /// ..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..
+    ///
+    /// __3_2_6__
+    /// 9__3_5__1
+    /// __18_64__
+    /// __81_29__
+    /// 7_______8
+    /// __67_82__
+    /// __26_95__
+    /// 8__2_3__9
+    /// __5_1_3__
+    /// 003020600900305001001806400008102900700000008006708200002609500800203009005010300
+    /// ";
+    ///
+    /// let mut reader = input.as_bytes();
+    /// let mut sudokus = vec![];
+    /// while let Some(sudoku) = Sudoku::from_reader(&mut reader)? {
+    ///     sudokus.push(sudoku.unwrap());
+    /// }
+    /// assert_eq!(sudokus.len(), 3);
+    /// assert!(sudokus.iter().all(|&sudoku| sudoku == sudokus[0]));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// Returns `Ok(None)`, if the reader contains no further sudoku.
+    pub fn from_reader<R: io::BufRead>(reader: &mut R) -> io::Result<Option<Result<Sudoku, ParseError>>> {
+        let first_line = match read_nonblank_line(reader)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        if is_line_format(&first_line) {
+            return Ok(Some(Sudoku::from_str_line(&first_line).map_err(ParseError::Line)));
+        }
+        let block = read_block(reader, first_line)?;
+        Ok(Some(Sudoku::from_str_block(&block).map_err(ParseError::Block)))
+    }
+
     /// Reads a sudoku in a variety of block formats with very few constraints.
     ///
     /// '_', '.' and '0' are treated as empty cells. '1' to '9' as clues.
     /// Each line needs to have 9 valid cells.
     /// Lines that don't contain 9 valid entries are ignored.
+    /// All other characters, such as `|` and spaces, are skipped. This includes horizontal
+    /// separator lines like `------+------+------` or `+=======+=======+=======+`, so that
+    /// pretty printed grids as commonly found on forums can be read:
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let sudoku = Sudoku::from_str_block_permissive("
+    /// +-------+-------+-------+
+    /// | 4 . . | . . . | 8 . 5 |
+    /// | . 3 . | . . . | . . . |
+    /// | . . . | 7 . . | . . . |
+    /// +-------+-------+-------+
+    /// | . 2 . | . . . | . 6 . |
+    /// | . . . | . 8 . | 4 . . |
+    /// | . . . | . 1 . | . . . |
+    /// +-------+-------+-------+
+    /// | . . . | 6 . 3 | . 7 . |
+    /// | 5 . . | 2 . . | . . . |
+    /// | 1 . 4 | . . . | . . . |
+    /// +-------+-------+-------+
+    /// ").unwrap();
+    /// assert_eq!(
+    ///     sudoku.to_str_line().to_string(),
+    ///     "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+    /// );
+    /// ```
     ///
     /// Stops parsing after the first sudoku.
     ///
@@ -586,9 +851,59 @@ impl Sudoku {
         Err(NotEnoughRows(valid_rows as u8))
     }
 
+    /// Solve the sudoku without backtracking, using only the constraint propagation of the fast solver.
+    /// Returns `None`, if the sudoku can't be solved this way, including when it has no solution.
+    ///
+    /// The propagation consists of naked singles and locked candidates within bands.
+    /// It finds many, but not all hidden singles, so it is a fast but crude criterion for easy sudokus.
+    /// For grading with human strategies, see [`StrategySolver`].
+    pub fn solution_without_guessing(self) -> Option<Sudoku> {
+        SudokuSolver::from_sudoku(self)
+            .ok()
+            .and_then(SudokuSolver::solution_without_guessing)
+    }
+
+    /// Grade the sudoku by the hardest strategy the [`StrategySolver`] needs to solve it.
+    /// Returns `None`, if the strategies can't solve it, including when it doesn't have a unique solution.
+    ///
+    /// The solver uses the strategies of all [tiers](Tier::strategies), which leave out the strategies that
+    /// are only valid for sudokus with a unique solution. It tries them ordered by their
+    /// [score weight](crate::strategy::Strategy::SCORE_WEIGHTS) and starts over with the simplest after
+    /// each successful step, so harder ones are only used where necessary. The grade of each strategy is
+    /// listed in [`Strategy::difficulty`](crate::strategy::Strategy::difficulty), the result is the
+    /// [`difficulty`](crate::strategy::deduction::Deductions::difficulty) of the solving path.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::strategy::Difficulty;
+    ///
+    /// let sudoku = Sudoku::from_str_line(
+    ///     "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+    /// ).unwrap();
+    /// assert_eq!(sudoku.grade(), Some(Difficulty::Easy));
+    ///
+    /// // the empty grid has many solutions
+    /// assert_eq!(Sudoku::from_bytes([0; 81]).unwrap().grade(), None);
+    /// ```
+    pub fn grade(self) -> Option<Difficulty> {
+        let mut strategies = [Tier::Basic, Tier::Intermediate, Tier::Advanced, Tier::Expert]
+            .iter()
+            .flat_map(|tier| tier.strategies())
+            .cloned()
+            .collect::<Vec<_>>();
+        strategies.sort_by_key(|strategy| strategy.score_weight());
+        StrategySolver::from_sudoku(self)
+            .solve(&strategies)
+            .ok()
+            .map(|(_, deductions)| deductions.difficulty())
+    }
+
     /// Find a solution to the sudoku. When a solution is found, it immediately stops searching and can therefore not guarantee uniqueness.
     /// If there is a unique solution, this will find it in, on average, half the time as [`Sudoku::solution`].
     /// Return `None` if no solution exists.
+    ///
+    /// Which solution is returned for sudokus with multiple solutions is deterministic,
+    /// see [`solutions_up_to`](Self::solutions_up_to).
     pub fn possibly_nonunique_solution(self) -> Option<Sudoku> {
         let mut buf = [[0; N_CELLS]];
         match self.solutions_up_to_buffer(&mut buf, 1) == 1 {
@@ -597,7 +912,66 @@ impl Sudoku {
         }
     }
 
+    /// Returns the first solution without changing `self`, even if the solution is not unique.
+    /// Equivalent to [`possibly_nonunique_solution`](Self::possibly_nonunique_solution), but usable
+    /// through a shared reference, e.g. for sudokus in a read-only collection.
+    /// Return `None` if no solution exists.
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let puzzles = vec![Sudoku::empty()];
+    /// let solution = puzzles[0].solved().unwrap();
+    /// assert!(solution.is_solved());
+    /// assert_eq!(puzzles[0].n_clues(), 0);
+    /// ```
+    pub fn solved(&self) -> Option<Sudoku> {
+        self.possibly_nonunique_solution()
+    }
+
+    /// Like [`possibly_nonunique_solution`](Self::possibly_nonunique_solution), but also returns
+    /// statistics about the backtracking search that found the solution, such as the number of guesses.
+    /// Return `None` if no solution exists.
+    pub fn possibly_nonunique_solution_with_stats(self) -> Option<(Sudoku, SolveStats)> {
+        let mut buf = [[0; N_CELLS]];
+        let solver = SudokuSolver::from_sudoku(self).ok()?;
+        match solver.solutions_up_to_buffer_with_stats(&mut buf, 1) {
+            (1, stats) => Some((Sudoku(buf[0]), stats)),
+            _ => None,
+        }
+    }
+
+    /// Like [`possibly_nonunique_solution`](Self::possibly_nonunique_solution), but gives up with
+    /// `Err(Timeout)` once `deadline` has passed. The clock is checked periodically while guessing,
+    /// so the search can overrun the deadline slightly. Sudokus that need no guesses are always solved.
+    pub fn possibly_nonunique_solution_with_deadline(
+        self,
+        deadline: Instant,
+    ) -> Result<Option<Sudoku>, Timeout> {
+        let solver = match SudokuSolver::from_sudoku(self) {
+            Ok(solver) => solver,
+            Err(_) => return Ok(None),
+        };
+        let mut buf = [[0; N_CELLS]];
+        match solver.solutions_up_to_buffer_with_deadline(&mut buf, 1, deadline)? {
+            1 => Ok(Some(Sudoku(buf[0]))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a solution, if one exists, like [`possibly_nonunique_solution`](Self::possibly_nonunique_solution).
+    /// Otherwise, returns the partial grid with the most entries that the backtracking search reached before it
+    /// ran out of options, e.g. to show how far the solver got on a contradictory sudoku.
+    ///
+    /// The result is only a best effort and not a proof of anything. A partial grid contains all clues
+    /// and no entry conflicts with another one in the same house, but it can't be completed and a different
+    /// search could reach another one with more entries. If the clues already conflict with each other,
+    /// the sudoku is returned unchanged.
+    pub fn best_effort_solution(self) -> Sudoku {
+        SudokuSolver::from_sudoku(self).map_or(self, SudokuSolver::best_effort_solution)
+    }
+
     /// Solve sudoku and return solution if solution is unique.
+    /// The search stops as soon as a second solution is found.
     pub fn solution(self) -> Option<Sudoku> {
         // without at least 8 digits present, sudoku has multiple solutions
         // bitmask
@@ -622,6 +996,12 @@ impl Sudoku {
 
     /// Counts number of solutions to sudoku up to `limit`
     /// This solves the sudoku but does not return the solutions which allows for slightly faster execution.
+    ///
+    /// The search stops as soon as `limit` solutions are found, so this is fast even for sudokus with
+    /// astronomically many solutions. The [empty](Self::empty) grid has about 6.67×10²¹ solutions,
+    /// but `Sudoku::empty().solutions_count_up_to(2)` returns 2 after less than a hundred guesses.
+    ///
+    /// Every solution is counted only once.
     pub fn solutions_count_up_to(self, limit: usize) -> usize {
         SudokuSolver::from_sudoku(self)
             .ok()
@@ -634,17 +1014,175 @@ impl Sudoku {
         self.solutions_count_up_to(2) == 1
     }
 
+    /// Checks whether the sudoku is proper, i.e. has exactly one solution.
+    /// "Proper" is the common term for sudokus that are valid puzzles. Sudokus with no or multiple solutions
+    /// are improper.
+    ///
+    /// This is the same as [`is_uniquely_solvable`](Self::is_uniquely_solvable).
+    pub fn is_proper(self) -> bool {
+        self.is_uniquely_solvable()
+    }
+
     /// Solve sudoku and return the first `limit` solutions it finds. If less solutions exist, return only those. Return `None` if no solution exists.
-    /// No specific ordering of solutions is promised. It can change across versions.
+    /// Like [`solutions_count_up_to`](Self::solutions_count_up_to), the search stops at `limit`, even for the
+    /// empty grid.
+    /// No specific ordering of solutions is promised. It can change across versions, but within
+    /// a version the solver is deterministic: the same sudoku always gives the same solutions in the same order.
+    /// The returned solutions are always distinct, so their number is the number of solutions up to `limit`.
     pub fn solutions_up_to(self, limit: usize) -> Vec<Sudoku> {
         SudokuSolver::from_sudoku(self)
             .ok()
             .map_or(vec![], |solver| solver.solutions_up_to(limit))
     }
 
+    /// Checks whether the sudoku has no, one or multiple solutions and returns up to two of them
+    /// as proof. Takes as long as [`is_uniquely_solvable`](Self::is_uniquely_solvable).
+    ///
+    /// ```
+    /// # use sudoku::{Sudoku, Uniqueness};
+    /// match Sudoku::empty().uniqueness() {
+    ///     Uniqueness::Multiple(first, second) => assert!(!first.diff(&second).is_empty()),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn uniqueness(self) -> Uniqueness {
+        SudokuSolver::from_sudoku(self)
+            .ok()
+            .map_or(Uniqueness::NoSolution, SudokuSolver::uniqueness)
+    }
+
+    /// Checks whether the sudoku is a proper puzzle: its givens don't conflict and it has exactly one solution.
+    /// Returns the reason if it is not. Conflicting givens are reported before the solutions are counted.
+    ///
+    /// ```
+    /// # use sudoku::{GridError, Sudoku};
+    /// let sudoku = Sudoku::from_str_line(
+    ///     "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+    /// ).unwrap();
+    /// assert_eq!(sudoku.validate(), Ok(()));
+    /// assert_eq!(Sudoku::empty().validate(), Err(GridError::NotUnique));
+    ///
+    /// // a 6 in the first cell conflicts with the 6 in the same row
+    /// let mut bytes = sudoku.to_bytes();
+    /// bytes[0] = 6;
+    /// match Sudoku::from_bytes(bytes).unwrap().validate() {
+    ///     Err(GridError::DuplicateInHouse { cells }) => assert_eq!(cells.len(), 2),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<(), GridError> {
+        let conflicting_cells = self
+            .clues()
+            .filter(|&Candidate { cell, digit }| self.peer_digits(cell).contains(digit))
+            .fold(Set::NONE, |cells, candidate| cells | candidate.cell);
+        if !conflicting_cells.is_empty() {
+            return Err(GridError::DuplicateInHouse {
+                cells: conflicting_cells,
+            });
+        }
+
+        match self.uniqueness() {
+            Uniqueness::Unique(_) => Ok(()),
+            Uniqueness::NoSolution => Err(GridError::NoSolution),
+            Uniqueness::Multiple(..) => Err(GridError::NotUnique),
+        }
+    }
+
+    /// If the sudoku has at least 2 solutions, returns the indices of the cells in which the first two solutions found
+    /// differ. At least one of these cells needs an additional clue to make the sudoku uniquely solvable.
+    /// Returns `None`, if the sudoku has no or exactly one solution.
+    pub fn uniqueness_diff(self) -> Option<Vec<usize>> {
+        let mut solutions = [[0; N_CELLS]; 2];
+        if self.solutions_up_to_buffer(&mut solutions, 2) < 2 {
+            return None;
+        }
+        let [first, second] = solutions;
+        let differing_cells = (0..N_CELLS).filter(|&cell| first[cell] != second[cell]).collect();
+        Some(differing_cells)
+    }
+
+    /// Compares the sudoku cell by cell with `other` and returns all cells in which they differ
+    /// together with the content in `self` and in `other`. Empty cells are `None`.
+    ///
+    /// Comparing a partially filled grid with its solution lists both the wrong and the missing digits:
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// # use sudoku::board::{Cell, Digit};
+    /// let solution = Sudoku::generate_solved();
+    /// let mut progress = solution.to_bytes();
+    /// progress[0] = 0;
+    /// progress[1] = progress[2];
+    /// let progress = Sudoku::from_bytes(progress).unwrap();
+    ///
+    /// let diff = progress.diff(&solution);
+    /// assert_eq!(diff.len(), 2);
+    /// assert_eq!(diff[0], (Cell::new(0), None, Digit::new_checked(solution.to_bytes()[0])));
+    /// ```
+    pub fn diff(&self, other: &Sudoku) -> Vec<(Cell, Option<Digit>, Option<Digit>)> {
+        Cell::all()
+            .zip(self.0.iter().zip(other.0.iter()))
+            .filter(|(_, (own, other))| own != other)
+            .map(|(cell, (&own, &other))| (cell, Digit::new_checked(own), Digit::new_checked(other)))
+            .collect()
+    }
+
+    /// Returns all minimal unavoidable sets of up to `max_size` cells of a solved sudoku, sorted by size.
+    /// The sudoku must be a complete solution grid, otherwise an empty list is returned.
+    ///
+    /// An unavoidable set is a set of cells whose digits can be rearranged to give another
+    /// valid solution grid. Every puzzle with a unique solution must have a clue in each
+    /// unavoidable set. It is minimal, if it does not contain a smaller unavoidable set.
+    ///
+    /// The search is exhaustive, but its cost grows quickly with `max_size`. Sets of up to 9 cells
+    /// take a fraction of a second. Larger sets are not supported, a `max_size` above 9 is treated as 9.
+    /// For 10 cells, the search would have to go through about a hundred million solutions.
+    pub fn unavoidable_sets(self, max_size: usize) -> Vec<Set<Cell>> {
+        // a set of up to 9 cells contains at most 4 different digits
+        const MAX_SIZE: usize = 9;
+
+        if !self.is_solved() {
+            return vec![];
+        }
+        let max_size = max_size.min(MAX_SIZE);
+
+        // Each digit of an unavoidable set occurs in it at least twice. Blanking all cells with
+        // the digits of the set and finding all solutions of the resulting puzzle will therefore
+        // give a solution that differs from this one in exactly the cells of the set.
+        let mut sets = vec![];
+        for digits in (0..1u16 << 9).filter(|digits| digits.count_ones() as usize * 2 <= max_size) {
+            let mut puzzle = self;
+            for cell in puzzle.0.iter_mut() {
+                if digits & 1 << (*cell - 1) != 0 {
+                    *cell = 0;
+                }
+            }
+            for solution in puzzle.solutions_up_to(usize::MAX) {
+                let set = (0..N_CELLS as u8)
+                    .filter(|&cell| solution.0[cell as usize] != self.0[cell as usize])
+                    .map(|cell| Cell::new(cell).as_set())
+                    .fold(Set::NONE, ops::BitOr::bitor);
+                if !set.is_empty() && set.len() <= max_size {
+                    sets.push(set);
+                }
+            }
+        }
+
+        sets.sort_by_key(|set| (set.len(), set.bits()));
+        sets.dedup();
+        let mut minimal_sets: Vec<Set<Cell>> = vec![];
+        for set in sets {
+            if !minimal_sets.iter().any(|&smaller| set.contains(smaller)) {
+                minimal_sets.push(set);
+            }
+        }
+        minimal_sets
+    }
+
     /// Counts number of solutions to sudoku up to `limit` and writes any solution found into `target`
     /// up to its capacity. Additional solutions will be counted but not saved.
-    /// No specific ordering of solutions is promised. It can change across versions.
+    /// No specific ordering of solutions is promised. It can change across versions, but within
+    /// a version the solver is deterministic: the same sudoku always gives the same solutions in the same order.
     /// This is primarily meant for C FFI.
     pub fn solutions_up_to_buffer(self, target: &mut [[u8; N_CELLS]], limit: usize) -> usize {
         SudokuSolver::from_sudoku(self)
@@ -678,11 +1216,81 @@ impl Sudoku {
         house_digits == HouseArray([Set::ALL; N_HOUSES])
     }
 
+    /// Checks whether no digit appears more than once in any row, column or block.
+    ///
+    /// Empty cells are ignored, so partially filled grids can be valid. Whether the sudoku can be completed
+    /// is not checked, use [`validate`](Self::validate) to also count the solutions.
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let mut bytes = [0; 81];
+    /// bytes[0] = 5;
+    /// assert!(Sudoku::from_bytes(bytes).unwrap().is_valid());
+    /// bytes[8] = 5;
+    /// assert!(!Sudoku::from_bytes(bytes).unwrap().is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        use crate::helper::HouseArray;
+
+        // collection of digit sets for all 9 rows, 9 cols and 9 blocks
+        let mut house_digits = HouseArray([Set::NONE; N_HOUSES]);
+
+        for (cell, &content) in Cell::all().zip(self.0.iter()) {
+            let digit = match Digit::new_checked(content) {
+                None => continue,
+                Some(digit) => digit,
+            };
+
+            for &house in cell.houses().iter() {
+                if house_digits[house].contains(digit) {
+                    return false;
+                }
+                house_digits[house] |= digit;
+            }
+        }
+        true
+    }
+
+    /// Returns the set of digits entered in the peers of `cell`, i.e. in its row, column and block.
+    /// The cell's own digit is not included.
+    ///
+    /// None of these digits can be placed in `cell`, so `Set::ALL.without(sudoku.peer_digits(cell))`
+    /// contains its candidates, before any strategies are applied.
+    pub fn peer_digits(&self, cell: Cell) -> Set<Digit> {
+        cell.peer_set()
+            .into_iter()
+            .filter_map(|peer| Digit::new_checked(self.0[peer.as_index()]))
+            .fold(Set::NONE, |digits, digit| digits | digit.as_set())
+    }
+
     /// Returns number of filled cells
     pub fn n_clues(&self) -> u8 {
         self.0.iter().filter(|&&num| num != 0).count() as u8
     }
 
+    /// Returns an iterator over the filled cells as [`Candidate`]s, i.e. pairs of [`Cell`] and [`Digit`].
+    /// They are in cell order, from left to right, top to bottom, like [`iter`](Self::iter).
+    /// The number of items equals [`n_clues`](Self::n_clues).
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// # use sudoku::board::{Candidate, Cell, Digit};
+    /// let mut bytes = [0; 81];
+    /// bytes[80] = 9;
+    /// bytes[4] = 2;
+    /// let sudoku = Sudoku::from_bytes(bytes).unwrap();
+    ///
+    /// let clues = sudoku.clues().map(|Candidate { cell, digit }| (cell, digit)).collect::<Vec<_>>();
+    /// assert_eq!(clues, [(Cell::new(4), Digit::new(2)), (Cell::new(80), Digit::new(9))]);
+    /// assert_eq!(sudoku.n_clues(), 2);
+    /// ```
+    pub fn clues(self) -> impl Iterator<Item = Candidate> {
+        (0..N_CELLS as u8).filter_map(move |cell| match self.0[cell as usize] {
+            0 => None,
+            digit => Some(Candidate::new(cell, digit)),
+        })
+    }
+
     /// Perform various transformations that create a different but equivalent sudoku.
     /// The transformations preserve the sudoku's validity and the amount of solutions
     /// as well a the applicability of solution strategies.
@@ -714,6 +1322,21 @@ impl Sudoku {
         self
     }
 
+    /// Returns a copy of the sudoku rotated by 180°. The clues of a sudoku with
+    /// [`Symmetry::HalfRotation`] stay in the same cells, but their digits may change.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    ///
+    /// let sudoku = Sudoku::generate();
+    /// assert_eq!(sudoku.rotate180().rotate180(), sudoku);
+    /// ```
+    pub fn rotate180(&self) -> Sudoku {
+        let mut sudoku = *self;
+        sudoku.0.reverse();
+        sudoku
+    }
+
     /// Returns the canonical representation of this sudoku and its automorphism count.
     ///
     /// All sudokus that can be translated into each other via validity preserving transformations belong to the same
@@ -750,15 +1373,57 @@ impl Sudoku {
         self.0.iter().map(num_to_opt)
     }
 
+    /// Returns an iterator over the contents of the 9 rows, from top to bottom.
+    /// The cells of each row are ordered from left to right. Empty cells are `None`.
+    pub fn rows(&self) -> impl Iterator<Item = [Option<u8>; 9]> {
+        self.houses(0..9)
+    }
+
+    /// Returns an iterator over the contents of the 9 columns, from left to right.
+    /// The cells of each column are ordered from top to bottom. Empty cells are `None`.
+    pub fn cols(&self) -> impl Iterator<Item = [Option<u8>; 9]> {
+        self.houses(9..18)
+    }
+
+    /// Returns an iterator over the contents of the 9 blocks, from left to right, top to bottom.
+    /// The cells of each block are ordered in the same way. Empty cells are `None`.
+    pub fn blocks(&self) -> impl Iterator<Item = [Option<u8>; 9]> {
+        self.houses(18..27)
+    }
+
+    fn houses(&self, houses: ops::Range<u8>) -> impl Iterator<Item = [Option<u8>; 9]> {
+        let sudoku = *self;
+        houses.map(move |house| {
+            let mut contents = [None; 9];
+            for (content, cell) in contents.iter_mut().zip(House::new(house).cells()) {
+                *content = num_to_opt(&sudoku.0[cell.as_index()]);
+            }
+            contents
+        })
+    }
+
     /// Returns a byte array for the sudoku.
     /// Empty cells are denoted by 0, clues by the numbers 1-9.
     pub fn to_bytes(self) -> [u8; N_CELLS] {
         self.0
     }
 
+    /// Returns a 2D array for the sudoku in row-major order, i.e. `grid[row][col]`.
+    /// Empty cells are denoted by 0, clues by the numbers 1-9.
+    pub fn to_2d(&self) -> [[u8; 9]; 9] {
+        let mut grid = [[0; 9]; 9];
+        for (row, row_bytes) in grid.iter_mut().zip(self.0.chunks(9)) {
+            row.copy_from_slice(row_bytes);
+        }
+        grid
+    }
+
     /// Returns a representation of the sudoku in line format that can be printed
     /// and which derefs into a &str
     ///
+    /// It consists of exactly 81 characters, the digits of the clues and `'.'` for empty cells,
+    /// and is read back by [`from_str_line`](Self::from_str_line).
+    ///
     /// ```
     /// use sudoku::Sudoku;
     ///
@@ -785,6 +1450,36 @@ impl Sudoku {
         SudokuLine(chars)
     }
 
+    /// Returns the sudoku in the line format, followed by a space and its [checksum](Self::checksum)
+    /// as 4 uppercase hexadecimal digits. It can be read with [`from_str_line_checked`](Self::from_str_line_checked)
+    /// and, as the checksum is treated as a comment, also with [`from_str_line`](Self::from_str_line).
+    ///
+    /// ```
+    /// # use sudoku::Sudoku;
+    /// let line = Sudoku::empty().to_str_line_checked();
+    /// assert_eq!(&line[81..], " 02FE");
+    /// assert_eq!(Sudoku::from_str_line_checked(&line), Ok(Sudoku::empty()));
+    /// ```
+    pub fn to_str_line_checked(&self) -> String {
+        format!("{} {:04X}", self.to_str_line(), self.checksum())
+    }
+
+    /// Returns a checksum of the sudoku for detecting corrupted data.
+    /// It is the CRC-16/CCITT-FALSE of the 81 bytes as returned by [`to_bytes`](Self::to_bytes).
+    pub fn checksum(&self) -> u16 {
+        let mut crc = 0xFFFF_u16;
+        for &byte in self.0.iter() {
+            crc ^= u16::from(byte) << 8;
+            for _ in 0..8 {
+                crc = match crc & 0x8000 != 0 {
+                    true => (crc << 1) ^ 0x1021,
+                    false => crc << 1,
+                };
+            }
+        }
+        crc
+    }
+
     /// Returns a value that, prints a block representation of the sudoku
     /// when formatted via the `Display` trait.
     ///
@@ -820,6 +1515,61 @@ impl Sudoku {
     }
 }
 
+// Reads lines until one isn't blank and returns it. Returns `None`, if the reader ends before.
+fn read_nonblank_line<R: io::BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            return Ok(Some(line));
+        }
+    }
+}
+
+// Reads the remaining lines of a sudoku in the block format that starts with `first_line`,
+// i.e. 9 lines in total or 11 with field delimiters.
+fn read_block<R: io::BufRead>(reader: &mut R, first_line: String) -> io::Result<String> {
+    let mut block = first_line;
+    let mut n_lines = 1;
+    let mut is_delimited = false;
+    let mut line = String::new();
+    loop {
+        if !block.ends_with('\n') {
+            block.push('\n');
+        }
+        if n_lines == 11 || (n_lines == 9 && !is_delimited) {
+            break;
+        }
+
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        block.push_str(&line);
+        n_lines += 1;
+
+        // a horizontal field delimiter after the 3rd row adds 2 lines to the sudoku
+        if n_lines == 4 {
+            is_delimited = line.starts_with('-');
+        }
+    }
+    Ok(block)
+}
+
+// Whether `line` is a sudoku in the line format rather than the first row of one in the block format,
+// judging by the length before the comment delimiter. Rows of the block format have 9 cells
+// and up to 2 field delimiters.
+fn is_line_format(line: &str) -> bool {
+    let cells = line
+        .split(|ch| [' ', '\t', '\r', '\n'].contains(&ch))
+        .next()
+        .unwrap();
+    cells.chars().count() > 11
+}
+
 #[rustfmt::skip]
 #[allow(clippy::trivially_copy_pass_by_ref)]
 fn num_to_opt(num: &u8) -> Option<u8> {
@@ -832,6 +1582,99 @@ impl fmt::Display for Sudoku {
     }
 }
 
+/// Equivalent to [`Sudoku::from_bytes`].
+impl TryFrom<[u8; N_CELLS]> for Sudoku {
+    type Error = FromBytesError;
+
+    fn try_from(bytes: [u8; N_CELLS]) -> Result<Sudoku, FromBytesError> {
+        Sudoku::from_bytes(bytes)
+    }
+}
+
+/// Parses a sudoku in either the line or the block format, see [`Sudoku::from_reader`] for how the format is detected.
+///
+/// ```
+/// # use sudoku::Sudoku;
+/// let line = "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+/// let block = "\
+/// __3_2_6__
+/// 9__3_5__1
+/// __18_64__
+/// __81_29__
+/// 7_______8
+/// __67_82__
+/// __26_95__
+/// 8__2_3__9
+/// __5_1_3__";
+/// assert_eq!(line.parse::<Sudoku>(), block.parse::<Sudoku>());
+/// ```
+impl str::FromStr for Sudoku {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Sudoku, ParseError> {
+        let start = s.len() - s.trim_start().len();
+        // back up to the start of the first non-blank line
+        let start = s[..start].rfind('\n').map_or(0, |newline| newline + 1);
+        let s = &s[start..];
+        match is_line_format(s) {
+            true => Sudoku::from_str_line(s).map_err(ParseError::Line),
+            false => Sudoku::from_str_block(s).map_err(ParseError::Block),
+        }
+    }
+}
+
+/// Returns the content of a cell, 0 for empty cells and the digit for clues, as in [`Sudoku::to_bytes`].
+///
+/// ```
+/// # use sudoku::Sudoku;
+/// # use sudoku::board::Cell;
+/// let mut bytes = [0; 81];
+/// bytes[4] = 7;
+/// let sudoku = Sudoku::from_bytes(bytes).unwrap();
+/// assert_eq!(sudoku[Cell::new(4)], 7);
+/// assert_eq!(sudoku[Cell::new(5)], 0);
+/// ```
+impl ops::Index<Cell> for Sudoku {
+    type Output = u8;
+
+    fn index(&self, cell: Cell) -> &u8 {
+        &self.0[cell.as_index()]
+    }
+}
+
+/// Returns the content of the cell at `(row, col)`, both counted from 0, like the index of [`Cell`].
+///
+/// ```
+/// # use sudoku::Sudoku;
+/// let mut grid = [[0; 9]; 9];
+/// grid[0][4] = 7;
+/// let sudoku = Sudoku::from_2d(grid).unwrap();
+/// assert_eq!(sudoku[(0, 4)], 7);
+/// ```
+///
+/// # Panics
+/// If `row` or `col` is 9 or above.
+impl ops::Index<(u8, u8)> for Sudoku {
+    type Output = u8;
+
+    fn index(&self, (row, col): (u8, u8)) -> &u8 {
+        assert!(
+            row < 9 && col < 9,
+            "index out of bounds: the row and column must be below 9 but are ({}, {})",
+            row,
+            col
+        );
+        &self.0[row as usize * 9 + col as usize]
+    }
+}
+
+/// Equivalent to [`Sudoku::to_bytes`].
+impl From<Sudoku> for [u8; N_CELLS] {
+    fn from(sudoku: Sudoku) -> [u8; N_CELLS] {
+        sudoku.to_bytes()
+    }
+}
+
 /// Container for the &str representation of a sudoku
 // MUST ALWAYS contain valid utf8
 #[derive(Copy, Clone)]
@@ -959,6 +1802,158 @@ mod test {
     use super::*;
     use strum::IntoEnumIterator;
 
+    #[test]
+    fn uniqueness_diff() {
+        let solved = Sudoku::from_str_line(
+            "123456789456789123789123456214365897365897214897214365531642978642978531978531642",
+        )
+        .unwrap();
+        assert_eq!(solved.uniqueness_diff(), None);
+
+        // The digits in rows 0 and 3 can be swapped in the columns 0 and 1 without breaking the sudoku.
+        let mut bytes = solved.to_bytes();
+        for &cell in &[0, 1, 27, 28] {
+            bytes[cell] = 0;
+        }
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+        assert_eq!(sudoku.uniqueness_diff(), Some(vec![0, 1, 27, 28]));
+    }
+
+    #[test]
+    fn generate_with_difficulty() {
+        for &difficulty in &[Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let sudoku = Sudoku::generate_with_difficulty(difficulty, 7).unwrap();
+            assert_eq!(Sudoku::generate_with_difficulty(difficulty, 7), Some(sudoku));
+            assert!(sudoku.is_uniquely_solvable());
+            assert_eq!(sudoku.grade(), Some(difficulty));
+        }
+    }
+
+    #[test]
+    fn peer_digits() {
+        let sudoku = Sudoku::from_str_line(
+            "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...",
+        )
+        .unwrap();
+        let digits = Set::from_digits;
+
+        assert_eq!(sudoku.peer_digits(Cell::new(0)), digits(&[1, 2, 3, 4, 5, 6]));
+        // the cell's own digit is excluded
+        assert_eq!(sudoku.peer_digits(Cell::new(3)), digits(&[3, 5, 6, 7]));
+        assert_eq!(
+            Sudoku::from_bytes([0; 81]).unwrap().peer_digits(Cell::new(40)),
+            Set::NONE
+        );
+    }
+
+    #[test]
+    fn clues() {
+        let sudoku = Sudoku::generate();
+        let clues = sudoku.clues().collect::<Vec<_>>();
+        assert_eq!(clues.len(), sudoku.n_clues() as usize);
+        // same order as `iter`
+        let filled_cells = sudoku
+            .iter()
+            .enumerate()
+            .filter(|(_, digit)| digit.is_some())
+            .map(|(cell, _)| cell);
+        assert!(clues
+            .iter()
+            .map(|candidate| candidate.cell.as_index())
+            .eq(filled_cells));
+        let mut grid = [0; 81];
+        for candidate in clues {
+            grid[candidate.cell.as_index()] = candidate.digit.get();
+        }
+        assert_eq!(Sudoku(grid), sudoku);
+    }
+
+    #[test]
+    fn index() {
+        let sudoku = Sudoku::generate();
+        let grid = sudoku.to_2d();
+        for cell in Cell::all() {
+            let (row, col) = (cell.get() / 9, cell.get() % 9);
+            assert_eq!(sudoku[cell], sudoku.0[cell.as_index()]);
+            assert_eq!(sudoku[(row, col)], grid[row as usize][col as usize]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the row and column must be below 9 but are (0, 9)")]
+    fn index_out_of_bounds() {
+        let _ = Sudoku::empty()[(0, 9)];
+    }
+
+    #[test]
+    fn houses() {
+        let sudoku = Sudoku::generate();
+        let cell = |row: usize, col: usize| num_to_opt(&sudoku.0[row * 9 + col]);
+
+        let rows = sudoku.rows().collect::<Vec<_>>();
+        let cols = sudoku.cols().collect::<Vec<_>>();
+        let blocks = sudoku.blocks().collect::<Vec<_>>();
+        assert_eq!((rows.len(), cols.len(), blocks.len()), (9, 9, 9));
+        for row in 0..9 {
+            for col in 0..9 {
+                let block = row / 3 * 3 + col / 3;
+                let block_pos = row % 3 * 3 + col % 3;
+                assert_eq!(rows[row][col], cell(row, col));
+                assert_eq!(cols[col][row], cell(row, col));
+                assert_eq!(blocks[block][block_pos], cell(row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Sudoku::empty(), Sudoku::default());
+        assert_eq!(Sudoku::empty().n_clues(), 0);
+    }
+
+    #[test]
+    fn unavoidable_sets() {
+        let solved = Sudoku::from_str_line(
+            "123456789456789123789123456214365897365897214897214365531642978642978531978531642",
+        )
+        .unwrap();
+        let sets = solved.unavoidable_sets(4);
+
+        let rectangle = [0, 1, 27, 28]
+            .iter()
+            .map(|&cell| Cell::new(cell).as_set())
+            .fold(Set::NONE, ops::BitOr::bitor);
+        assert!(sets.contains(&rectangle));
+        for (i, &set) in sets.iter().enumerate() {
+            assert_eq!(set.len(), 4);
+            assert!(sets[..i].iter().all(|&other| !set.contains(other)));
+
+            let mut sudoku = solved;
+            for cell in set {
+                sudoku.0[cell.as_index()] = 0;
+            }
+            assert!(!sudoku.is_uniquely_solvable());
+        }
+
+        assert!(Sudoku::from_bytes([0; N_CELLS])
+            .unwrap()
+            .unavoidable_sets(4)
+            .is_empty());
+    }
+
+    #[test]
+    fn byte_array_conversions() {
+        let mut bytes = [0; N_CELLS];
+        bytes[0] = 1;
+        bytes[80] = 9;
+        let sudoku = Sudoku::try_from(bytes).unwrap();
+        assert_eq!(sudoku, Sudoku::from_bytes(bytes).unwrap());
+        assert_eq!(<[u8; N_CELLS]>::from(sudoku)[..], bytes[..]);
+
+        bytes[40] = 10;
+        assert!(Sudoku::try_from(bytes).is_err());
+    }
+
     // each cell in a symmetry class must map to the same set of cells
     #[test]
     fn test_symmetry_all_cells_equivalent() {
@@ -978,6 +1973,44 @@ mod test {
         }
     }
 
+    #[test]
+    fn orbit() {
+        for symmetry in Symmetry::iter() {
+            // the orbits partition the grid
+            let mut covered = Set::NONE;
+            for cell in Cell::all() {
+                let orbit = cell.orbit(symmetry);
+                assert!(orbit.contains(&cell));
+                for &other in &orbit {
+                    assert_eq!(other.orbit(symmetry), orbit);
+                }
+                if orbit[0] == cell {
+                    covered = orbit.iter().fold(covered, |covered, &cell| covered | cell);
+                }
+            }
+            assert_eq!(covered, Set::ALL);
+        }
+        // every cell but the center has a partner under 180° rotation
+        let n_orbits = Cell::all()
+            .filter(|&cell| cell.orbit(Symmetry::HalfRotation)[0] == cell)
+            .count();
+        assert_eq!(n_orbits, 41);
+    }
+
+    #[test]
+    fn rotate180() {
+        let sudoku = Sudoku::generate_with_symmetry(Symmetry::HalfRotation);
+        let rotated = sudoku.rotate180();
+        for cell in 0..81 {
+            assert_eq!(rotated.0[cell], sudoku.0[80 - cell]);
+            assert_eq!(rotated.0[cell] == 0, sudoku.0[cell] == 0);
+        }
+        assert_eq!(
+            rotated.solution(),
+            sudoku.solution().map(|solution| solution.rotate180())
+        );
+    }
+
     // More complicated symmetries can be expressed as a combination of simple symmetries.
     #[test]
     fn test_symmetry_composite_symmetries() {
@@ -207,11 +207,11 @@ impl SudokuGenerator {
     }
 
     #[inline(always)]
-    fn find_good_random_guess(&mut self) -> Candidate {
+    fn find_good_random_guess(&mut self, rng: &mut impl Rng) -> Candidate {
         let best_cell = self.find_cell_min_poss();
         let poss_digits = self.cell_poss_digits[best_cell];
-        let choice = rand::thread_rng().gen_range(0, poss_digits.len());
-        let digit = poss_digits.into_iter().nth(choice as usize).unwrap();
+        let choice = rng.gen_range(0, poss_digits.len());
+        let digit = poss_digits.into_iter().nth(choice).unwrap();
         Candidate {
             digit,
             cell: best_cell,
@@ -235,7 +235,11 @@ impl SudokuGenerator {
     }
 
     // for generation of random, filled sudokus
-    fn randomized_solve_one(mut self, stack: &mut Vec<Candidate>) -> Result<Sudoku, Unsolvable> {
+    fn randomized_solve_one(
+        mut self,
+        stack: &mut Vec<Candidate>,
+        rng: &mut impl Rng,
+    ) -> Result<Sudoku, Unsolvable> {
         // insert and deduce in a loop
         // do a random guess when no more deductions are found
         // backtrack on error (via recursion)
@@ -250,9 +254,9 @@ impl SudokuGenerator {
                 continue;
             }
 
-            let entry = self.find_good_random_guess();
+            let entry = self.find_good_random_guess(rng);
             stack.push(entry);
-            if let filled_sudoku @ Ok(_) = self.clone().randomized_solve_one(stack) {
+            if let filled_sudoku @ Ok(_) = self.clone().randomized_solve_one(stack, rng) {
                 return filled_sudoku;
             }
             stack.clear();
@@ -261,12 +265,12 @@ impl SudokuGenerator {
         }
     }
 
-    pub fn generate_solved() -> Sudoku {
+    pub fn generate_solved(rng: &mut impl Rng) -> Sudoku {
         // fill first row with a permutation of 1...9
         // not necessary, but ~15% faster
         let mut stack = Vec::with_capacity(N_CELLS);
         let mut perm = [1, 2, 3, 4, 5, 6, 7, 8, 9];
-        rand::thread_rng().shuffle(&mut perm);
+        rng.shuffle(&mut perm);
 
         stack.extend(
             (0..9)
@@ -274,6 +278,6 @@ impl SudokuGenerator {
                 .map(|(cell, &digit)| Candidate::new(cell, digit)),
         );
 
-        Self::new().randomized_solve_one(&mut stack).unwrap()
+        Self::new().randomized_solve_one(&mut stack, rng).unwrap()
     }
 }
@@ -6,8 +6,16 @@ use crate::consts::N_CELLS;
 use std::fmt;
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 
-#[derive(Debug)]
-pub(crate) struct Unsolvable;
+/// Error for operations that found the sudoku to be impossible in its current state,
+/// e.g. because a cell would be left without candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Unsolvable;
+
+impl fmt::Display for Unsolvable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sudoku has no solution")
+    }
+}
 
 #[derive(Copy, Clone)]
 pub(crate) struct CellArray<T>(pub [T; N_CELLS]);
@@ -70,7 +78,7 @@ impl<T, IDX: Into<House>> IndexMut<IDX> for HouseArray<T> {
 }
 
 ///////////////////////////////
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) struct DigitArray<T>(pub [T; 9]);
 
 impl<T> Index<Digit> for DigitArray<T> {
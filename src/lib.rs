@@ -39,6 +39,12 @@
 //!     let cell_contents: [u8; 81] = solution.to_bytes();
 //! }
 //! ```
+//!
+//! ## Features
+//!
+//! - `serde`: Implements `Serialize` and `Deserialize` for `Sudoku`. Human readable formats like JSON
+//!   use the line format as a string, binary formats the 81 bytes of [`Sudoku::to_bytes`].
+//!   Parse errors are reported as custom errors of the deserializer. Off by default.
 
 pub mod bitset;
 pub mod board;
@@ -51,3 +57,5 @@ pub mod strategy;
 
 pub use crate::board::Sudoku;
 pub use crate::board::Symmetry;
+pub use crate::helper::Unsolvable;
+pub use crate::solver::{FastSolver, GridError, SolveStats, Solver, Timeout, Uniqueness};
@@ -1,4 +1,7 @@
 //! Errors that may be encountered when reading a sudoku from a string
+//!
+//! All errors implement `Display` and convert into `String`, for quick error handling with `?`
+//! in functions returning `Result<_, String>`.
 use crate::board::{block, col, row};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -66,6 +69,38 @@ pub enum LineParseError {
     TooManyCells,
     /// Comments must be delimited by a space or tab.
     MissingCommentDelimiter,
+    /// The checksum after the sudoku is missing or doesn't match, see [`Sudoku::from_str_line_checked`](crate::Sudoku::from_str_line_checked).
+    ChecksumMismatch,
+}
+
+impl fmt::Display for BlockParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        use self::BlockParseError as Error;
+
+        // rows and columns are counted from 1 in messages
+        match *self {
+            Error::InvalidEntry(entry) => write!(
+                f,
+                "row {}: invalid character '{}' in column {}",
+                entry.row() + 1,
+                entry.ch,
+                entry.col() + 1
+            ),
+            Error::InvalidLineLength(row) => write!(f, "row {}: row doesn't contain 9 cells", row + 1),
+            Error::NotEnoughRows(rows) => write!(f, "sudoku contains {} rows instead of required 9", rows),
+            Error::IncorrectFieldDelimiter => write!(f, "missing or misplaced field delimiter"),
+            Error::TooManyRows => write!(f, "sudoku contains more than 9 rows"),
+            Error::MissingCommentDelimiter(row) => write!(f, "row {}: missing comment delimiter", row + 1),
+        }
+    }
+}
+
+impl std::error::Error for BlockParseError {}
+
+impl From<BlockParseError> for String {
+    fn from(err: BlockParseError) -> String {
+        err.to_string()
+    }
 }
 
 impl fmt::Display for LineParseError {
@@ -84,6 +119,95 @@ impl fmt::Display for LineParseError {
                 "sudoku contains more than 81 cells or is missing comment delimiter"
             ),
             Error::MissingCommentDelimiter => write!(f, "missing comment delimiter"),
+            Error::ChecksumMismatch => write!(f, "checksum is missing or doesn't match"),
+        }
+    }
+}
+
+impl std::error::Error for LineParseError {}
+
+impl From<LineParseError> for String {
+    fn from(err: LineParseError) -> String {
+        err.to_string()
+    }
+}
+
+/// An error caused when creating a sudoku from raw numbers, see [`Sudoku::from_bytes`](crate::Sudoku::from_bytes).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum FromBytesError {
+    /// A number is above 9. Contains the index of the first such cell, counted from 0 in row-major order,
+    /// and its number.
+    InvalidNumber {
+        /// Cell number goes from 0..=80, 0..=8 for first line, 9..=17 for 2nd and so on
+        cell: u8,
+        /// The invalid number
+        number: u8,
+    },
+    /// The slice doesn't contain exactly 81 numbers. Contains its length.
+    WrongLength(usize),
+}
+
+impl fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            FromBytesError::InvalidNumber { cell, number } => {
+                write!(f, "cell {} contains {}, which is not from 0 to 9", cell, number)
+            }
+            FromBytesError::WrongLength(len) => write!(f, "{} numbers instead of required 81", len),
         }
     }
 }
+
+impl std::error::Error for FromBytesError {}
+
+impl From<FromBytesError> for String {
+    fn from(err: FromBytesError) -> String {
+        err.to_string()
+    }
+}
+
+/// An error caused when parsing a sudoku whose format is detected from the input,
+/// see [`Sudoku::from_str`](crate::Sudoku#impl-FromStr) and [`Sudoku::from_reader`](crate::Sudoku::from_reader).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ParseError {
+    /// The input was parsed in the line format
+    Line(LineParseError),
+    /// The input was parsed in the block format
+    Block(BlockParseError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseError::Line(err) => write!(f, "line format: {}", err),
+            ParseError::Block(err) => write!(f, "block format: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Line(err) => Some(err),
+            ParseError::Block(err) => Some(err),
+        }
+    }
+}
+
+impl From<LineParseError> for ParseError {
+    fn from(err: LineParseError) -> ParseError {
+        ParseError::Line(err)
+    }
+}
+
+impl From<BlockParseError> for ParseError {
+    fn from(err: BlockParseError) -> ParseError {
+        ParseError::Block(err)
+    }
+}
+
+impl From<ParseError> for String {
+    fn from(err: ParseError) -> String {
+        err.to_string()
+    }
+}
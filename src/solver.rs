@@ -38,9 +38,12 @@
 //  for a port under the AGPLv3 license in the forum thread
 //      http://forum.enjoysudoku.com/3-77us-solver-2-8g-cpu-testcase-17sodoku-t30470-270.html#p262718
 
+use crate::bitset::Set;
+use crate::board::Cell;
 use crate::helper::Unsolvable;
 use crate::Sudoku;
 use crunchy::unroll;
+use std::time::Instant;
 
 // masks of 27 bits
 const NONE: u32 = 0;
@@ -49,21 +52,175 @@ const LOW9: u32 = 0o000_000_777;
 
 // When the solver finds a solution it can save it or just count.
 // The latter is marginally faster.
-enum Solutions<'a> {
+enum Storage<'a> {
     Count(usize),
     Vector(&'a mut Vec<Sudoku>),
     Buffer(&'a mut [[u8; 81]], usize),
 }
 
-impl Solutions<'_> {
+// The found solutions together with statistics about the search.
+struct Solutions<'a> {
+    storage: Storage<'a>,
+    stats: SolveStats,
+    // number of nested guesses leading to the current state
+    depth: u32,
+    // the search is abandoned once the deadline has passed
+    deadline: Option<Instant>,
+    n_deadline_checks: u32,
+    timed_out: bool,
+    // the consistent state with the most entries reached so far and its number of entries, if requested
+    best_partial: Option<(u32, Sudoku)>,
+}
+
+impl<'a> Solutions<'a> {
+    fn new(storage: Storage<'a>) -> Self {
+        Solutions {
+            storage,
+            stats: SolveStats::default(),
+            depth: 0,
+            deadline: None,
+            n_deadline_checks: 0,
+            timed_out: false,
+            best_partial: None,
+        }
+    }
+
+    fn with_deadline(storage: Storage<'a>, deadline: Instant) -> Self {
+        Solutions {
+            deadline: Some(deadline),
+            ..Solutions::new(storage)
+        }
+    }
+
+    // Reading the clock is comparatively expensive, so it's only done for every 256th call.
+    fn deadline_passed(&mut self) -> bool {
+        if let Some(deadline) = self.deadline {
+            if !self.timed_out && self.n_deadline_checks & 0xff == 0 {
+                self.timed_out = Instant::now() >= deadline;
+            }
+            self.n_deadline_checks = self.n_deadline_checks.wrapping_add(1);
+        }
+        self.timed_out
+    }
+
     fn len(&self) -> usize {
-        match self {
-            Solutions::Vector(v) => v.len(),
-            Solutions::Count(len) => *len,
-            Solutions::Buffer(_, len) => *len,
+        match &self.storage {
+            Storage::Vector(v) => v.len(),
+            Storage::Count(len) => *len,
+            Storage::Buffer(_, len) => *len,
         }
     }
 }
+
+/// Error for searches that were abandoned because their deadline passed,
+/// see [`Sudoku::possibly_nonunique_solution_with_deadline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timeout;
+
+impl std::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "deadline passed before the search was finished")
+    }
+}
+
+/// Something that can solve sudokus, for code that should be generic over the solving backend.
+///
+/// Implemented by [`FastSolver`] for the backtracking solver and by `&[Strategy]` for the
+/// [`StrategySolver`](crate::strategy::StrategySolver) restricted to the given strategies.
+///
+/// ```
+/// # use sudoku::{FastSolver, Solver, Sudoku};
+/// # use sudoku::strategy::Strategy;
+/// fn solve_all(solver: impl Solver + Copy, sudokus: &[Sudoku]) -> Vec<Option<Sudoku>> {
+///     sudokus.iter().map(|&sudoku| solver.solve(sudoku)).collect()
+/// }
+///
+/// let easy = Sudoku::from_str_line(
+///     "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+/// ).unwrap();
+/// let harder = Sudoku::from_str_line(
+///     "......9.7...42.18....7.5.261..9.4....5.....4....5.7..992.1.8....34.59...5.7......",
+/// ).unwrap();
+/// let sudokus = [easy, harder];
+/// assert_eq!(solve_all(FastSolver, &sudokus), vec![easy.solution(), harder.solution()]);
+///
+/// let singles: &[Strategy] = &[Strategy::NakedSingles, Strategy::HiddenSingles];
+/// assert_eq!(solve_all(singles, &sudokus), vec![easy.solution(), None]);
+/// ```
+pub trait Solver {
+    /// Returns a solution of `sudoku`, or `None` if this solver can't find one.
+    fn solve(self, sudoku: Sudoku) -> Option<Sudoku>;
+}
+
+/// The fast backtracking solver behind [`Sudoku::solution`] and related methods.
+/// As a [`Solver`], it returns the first solution found, even if it is not unique,
+/// like [`Sudoku::possibly_nonunique_solution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FastSolver;
+
+impl Solver for FastSolver {
+    fn solve(self, sudoku: Sudoku) -> Option<Sudoku> {
+        sudoku.possibly_nonunique_solution()
+    }
+}
+
+/// Statistics about the backtracking search of the fast solver.
+/// They can serve as a rough proxy for the hardness of a sudoku.
+///
+/// The numbers depend on the solver's internal strategies and guessing order
+/// and can change across versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SolveStats {
+    /// The number of times a candidate was tentatively entered.
+    pub guesses: u32,
+    /// The highest number of nested guesses on the path to any explored state.
+    pub max_depth: u32,
+}
+
+/// The result of checking a sudoku for a unique solution, together with the solutions found.
+///
+/// If the sudoku has multiple solutions, the first two found serve as a witness.
+/// The cells in which they differ can be obtained with [`Sudoku::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Uniqueness {
+    /// The sudoku has no solution.
+    NoSolution,
+    /// The sudoku has exactly this solution.
+    Unique(Sudoku),
+    /// The sudoku has at least these two solutions.
+    Multiple(Sudoku, Sudoku),
+}
+
+/// The reason why a sudoku is not a valid puzzle, see [`Sudoku::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GridError {
+    /// Some givens conflict with each other, because a digit appears multiple times in a house.
+    DuplicateInHouse {
+        /// All cells whose digit appears again in one of their houses.
+        cells: Set<Cell>,
+    },
+    /// The givens don't conflict, but they can't be completed to a solution.
+    NoSolution,
+    /// The sudoku has more than one solution.
+    NotUnique,
+}
+
+impl std::fmt::Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            GridError::DuplicateInHouse { cells } => {
+                write!(
+                    f,
+                    "{} givens conflict with another given in the same house",
+                    cells.len()
+                )
+            }
+            GridError::NoSolution => write!(f, "sudoku has no solution"),
+            GridError::NotUnique => write!(f, "sudoku has multiple solutions"),
+        }
+    }
+}
+
 // Bands  Rows                   Columns
 //
 //               0    1    2    3    4    5    6    7    8
@@ -100,6 +257,11 @@ impl Solutions<'_> {
 // represented by one u32 with up to 27 bits set.
 // They are enumerated as
 // subband = digit * 3 + band
+//
+// The whole state consists of a few fixed-size arrays and holds no heap allocations,
+// so it is cheap to copy for guessing. For the same reason, creating a new solver for every
+// sudoku costs as much as resetting an existing one would and there is nothing to reuse
+// when solving many sudokus in a row.
 #[derive(Clone, Copy)]
 pub(crate) struct SudokuSolver {
     // possible_cells_in_subband = subbands[digit*3 + band]
@@ -133,21 +295,86 @@ impl SudokuSolver {
     /// Find and return up to `limit` solutions
     pub fn solutions_up_to(self, limit: usize) -> Vec<Sudoku> {
         let mut solutions = vec![];
-        self._solutions_up_to(limit, &mut Solutions::Vector(&mut solutions));
+        self._solutions_up_to(limit, &mut Solutions::new(Storage::Vector(&mut solutions)));
         solutions
     }
 
     /// Count up to `limit` solutions and save up to buffer.len() of them
     /// in `buffer`. Returns number of solutions.
     pub fn solutions_up_to_buffer(self, buffer: &mut [[u8; 81]], limit: usize) -> usize {
-        let mut solutions = Solutions::Buffer(buffer, 0);
+        let mut solutions = Solutions::new(Storage::Buffer(buffer, 0));
         self._solutions_up_to(limit, &mut solutions);
         solutions.len()
     }
 
+    /// Like [`solutions_up_to_buffer`](Self::solutions_up_to_buffer), but also returns statistics about the search.
+    pub fn solutions_up_to_buffer_with_stats(
+        self,
+        buffer: &mut [[u8; 81]],
+        limit: usize,
+    ) -> (usize, SolveStats) {
+        let mut solutions = Solutions::new(Storage::Buffer(buffer, 0));
+        self._solutions_up_to(limit, &mut solutions);
+        (solutions.len(), solutions.stats)
+    }
+
+    /// Like [`solutions_up_to_buffer`](Self::solutions_up_to_buffer), but abandons the search
+    /// once `deadline` has passed.
+    pub fn solutions_up_to_buffer_with_deadline(
+        self,
+        buffer: &mut [[u8; 81]],
+        limit: usize,
+        deadline: Instant,
+    ) -> Result<usize, Timeout> {
+        let mut solutions = Solutions::with_deadline(Storage::Buffer(buffer, 0), deadline);
+        self._solutions_up_to(limit, &mut solutions);
+        match solutions.timed_out {
+            true => Err(Timeout),
+            false => Ok(solutions.len()),
+        }
+    }
+
+    /// Find a solution or, if there is none, the state with the most entries that the search reached
+    /// without running into a contradiction. The latter is not part of any solution.
+    pub fn best_effort_solution(self) -> Sudoku {
+        let mut buffer = [[0; 81]];
+        let mut solutions = Solutions::new(Storage::Buffer(&mut buffer, 0));
+        solutions.best_partial = Some((0, Sudoku::empty()));
+        let clues = self.extract_partial_solution();
+
+        self._solutions_up_to(1, &mut solutions);
+        match (solutions.len(), solutions.best_partial) {
+            (1, _) => Sudoku(buffer[0]),
+            // the propagation alone already ran into a contradiction
+            (_, Some((0, _))) | (_, None) => clues,
+            (_, Some((_, partial))) => partial,
+        }
+    }
+
+    /// Search for up to 2 solutions and return them as a proof of (non-)uniqueness
+    pub fn uniqueness(self) -> Uniqueness {
+        let mut buffer = [[0; 81]; 2];
+        match self.solutions_up_to_buffer(&mut buffer, 2) {
+            0 => Uniqueness::NoSolution,
+            1 => Uniqueness::Unique(Sudoku(buffer[0])),
+            _ => Uniqueness::Multiple(Sudoku(buffer[0]), Sudoku(buffer[1])),
+        }
+    }
+
+    /// Solve the sudoku by propagation alone, without any guesses.
+    /// Returns `None`, if the propagation gets stuck or runs into a contradiction.
+    pub fn solution_without_guessing(mut self) -> Option<Sudoku> {
+        self.find_naked_singles().ok()?;
+        self._solve(1, &mut Solutions::new(Storage::Count(0))).ok()?;
+        match self.is_solved() {
+            true => Some(self.extract_solution()),
+            false => None,
+        }
+    }
+
     /// Find up to `limit` solutions and return count
     pub fn solutions_count_up_to(self, limit: usize) -> usize {
-        let mut solutions = Solutions::Count(0);
+        let mut solutions = Solutions::new(Storage::Count(0));
         self._solutions_up_to(limit, &mut solutions);
         solutions.len()
     }
@@ -173,7 +400,7 @@ impl SudokuSolver {
     // jczsolve equivalent: FullUpdate
     fn _solve(&mut self, limit: usize, solutions: &mut Solutions) -> Result<(), Unsolvable> {
         debug_assert!(solutions.len() <= limit);
-        if solutions.len() == limit {
+        if solutions.len() == limit || solutions.timed_out {
             return Err(Unsolvable); // not really, but it forces a recursion stop
         }
         loop {
@@ -343,23 +570,45 @@ impl SudokuSolver {
     fn guess(&mut self, limit: usize, solutions: &mut Solutions) {
         if self.is_solved() {
             debug_assert!(solutions.len() < limit);
-            match solutions {
-                Solutions::Count(count) => *count += 1,
-                Solutions::Vector(vec) => vec.push(self.extract_solution()),
-                Solutions::Buffer(buf, len) => {
+            // Every guess splits the search into branches that differ in at least one cell,
+            // so no solution can be found twice.
+            match &mut solutions.storage {
+                Storage::Count(count) => *count += 1,
+                Storage::Vector(vec) => vec.push(self.extract_solution()),
+                Storage::Buffer(buf, len) => {
                     if let Some(sudoku_slot) = buf.get_mut(*len) {
                         *sudoku_slot = self.extract_solution().to_bytes();
                     }
                     *len += 1;
                 }
             }
-        } else if self.guess_bivalue_in_cell(limit, solutions).is_ok() {
-            // .is_ok() == found nothing
-            self.guess_some_cell(limit, solutions);
+        } else if !solutions.deadline_passed() {
+            if let Some((n_best, best)) = &mut solutions.best_partial {
+                let n_entries = 81
+                    - self
+                        .unsolved_cells
+                        .0
+                        .iter()
+                        .map(|cells| cells.count_ones())
+                        .sum::<u32>();
+                if n_entries > *n_best {
+                    *n_best = n_entries;
+                    *best = self.extract_partial_solution();
+                }
+            }
+            solutions.depth += 1;
+            solutions.stats.max_depth = u32::max(solutions.stats.max_depth, solutions.depth);
+            if self.guess_bivalue_in_cell(limit, solutions).is_ok() {
+                // .is_ok() == found nothing
+                self.guess_some_cell(limit, solutions);
+            }
+            solutions.depth -= 1;
         }
     }
 
     /// Find some cell with only 2 possible values and try both in order.
+    /// The cell is the first one found in band order and the lower digit is tried first.
+    /// Guessing involves no randomness, so the order of solutions is always the same.
     //
     // Whenever a guess has to be taken, there is virtually always a cell
     // with only 2 possibilities. These positions are found and saved when
@@ -385,12 +634,14 @@ impl SudokuSolver {
                         first = false;
                         let mut solver = *self;
                         solver.insert_candidate_by_mask(subband, cell_mask);
+                        solutions.stats.guesses += 1;
                         if solver._solve(limit, solutions).is_ok() {
                             solver.guess(limit, solutions);
                         }
                         self.poss_cells[subband] ^= cell_mask;
                     } else {
                         self.insert_candidate_by_mask(subband, cell_mask);
+                        solutions.stats.guesses += 1;
                         if self._solve(limit, solutions).is_ok() {
                             self.guess(limit, solutions);
                         }
@@ -440,6 +691,7 @@ impl SudokuSolver {
             if self.poss_cells[subband] & unsolved_cell != NONE {
                 let mut solver = *self;
                 solver.insert_candidate_by_mask(subband, unsolved_cell);
+                solutions.stats.guesses += 1;
                 if solver._solve(limit, solutions).is_ok() {
                     solver.guess(limit, solutions);
                 }
@@ -491,6 +743,53 @@ impl SudokuSolver {
         Ok(())
     }
 
+    /// Undo the insertion of the clue `num` in `cell`, leaving the solver in the same state as
+    /// `SudokuSolver::from_sudoku(sudoku)` would, without rebuilding it from all clues.
+    /// `sudoku` is the grid after the clue was removed.
+    ///
+    /// The solver must not have been used for solving yet.
+    // This is used for removing clues one by one during generation.
+    pub(crate) fn remove_clue(&mut self, sudoku: &Sudoku, cell: u8, num: u8) {
+        debug_assert_eq!(sudoku.0[cell as usize], 0);
+        let band = (cell / 27) as usize;
+        let cell_mask = 1 << (cell % 27);
+        self.unsolved_cells[band] |= cell_mask;
+
+        // the cell gets back all digits that no peer was entered for
+        // the removed digit is still set from the insertion
+        let peer_digits = sudoku.peer_digits(Cell::new(cell));
+        for digit in !peer_digits {
+            self.poss_cells[digit.as_index() * 3 + band] |= cell_mask;
+        }
+
+        // peers may have lost the removed digit only because of this clue
+        // recompute its subbands from the remaining clues of the same digit
+        let digit_subband = (num as usize - 1) * 3;
+        let mut clue_cells = [NONE; 3];
+        for (other_cell, &other_num) in (0..81).zip(sudoku.0.iter()) {
+            if other_num == num {
+                clue_cells[(other_cell / 27) as usize] |= 1 << (other_cell % 27);
+            }
+        }
+        for (band, &clues) in clue_cells.iter().enumerate() {
+            self.poss_cells[digit_subband + band] = self.unsolved_cells[band] | clues;
+        }
+        for (band, &clues) in clue_cells.iter().enumerate() {
+            let subband = digit_subband + band;
+            for clue_mask in mask_iter(clues) {
+                let other_cell = band * 27 + bit_pos(clue_mask);
+                self.poss_cells[subband] &= nonconflicting_cells_same_band(other_cell);
+                let nonconflicting_other = nonconflicting_cells_neighbor_bands(other_cell as u8);
+                let (ns1, ns2) = neighbor_subbands(subband);
+                self.poss_cells[ns1] &= nonconflicting_other;
+                self.poss_cells[ns2] &= nonconflicting_other;
+            }
+        }
+        for (band, &clues) in clue_cells.iter().enumerate() {
+            self.poss_cells[digit_subband + band] |= clues;
+        }
+    }
+
     /// Insert the digit of `subband` in the (unique) position of `mask`.
     /// All conflicting cells (row and box neighbors) in the band have this digit
     /// candidate eliminated.
@@ -525,6 +824,21 @@ impl SudokuSolver {
         }
         Sudoku(sudoku)
     }
+
+    // Like `extract_solution`, but leaves unsolved cells empty
+    fn extract_partial_solution(&self) -> Sudoku {
+        let mut sudoku = [0; 81];
+        for (subband, &mask) in (0..27).zip(self.poss_cells.0.iter()) {
+            let digit = subband / 3;
+            let band = subband % 3;
+            let base_cell_in_band = band * 27;
+            for cell_mask in mask_iter(mask & !self.unsolved_cells[band]) {
+                let cell_in_band = bit_pos(cell_mask);
+                *index_mut(&mut sudoku, cell_in_band + base_cell_in_band) = digit as u8 + 1;
+            }
+        }
+        Sudoku(sudoku)
+    }
 }
 
 // ----------------------------------------------------------------
@@ -955,3 +1269,64 @@ fn mask_iter(mask: u32) -> impl Iterator<Item = u32> {
         Some(lowest_bit)
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{prng::ChaChaRng, Rng, SeedableRng};
+
+    #[test]
+    fn search_stops_at_limit() {
+        // only the first two rows are given, so there are thousands of solutions
+        let solved = Sudoku::from_str_line(
+            "123456789456789123789123456214365897365897214897214365531642978642978531978531642",
+        )
+        .unwrap();
+        let mut bytes = solved.to_bytes();
+        for byte in &mut bytes[18..] {
+            *byte = 0;
+        }
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+        assert_eq!(sudoku.solution(), None);
+
+        let mut buffer = [[0; 81]; 2];
+        let solver = SudokuSolver::from_sudoku(sudoku).unwrap();
+        let (n_solutions, stats) = solver.solutions_up_to_buffer_with_stats(&mut buffer, 2);
+        assert_eq!(n_solutions, 2);
+        assert_ne!(buffer[0], buffer[1]);
+
+        let (n_solutions, stats_1000) = solver.solutions_up_to_buffer_with_stats(&mut [], 1000);
+        assert_eq!(n_solutions, 1000);
+        // every solution requires at least one more guess, so a search that didn't stop at the
+        // second solution would need many more guesses
+        assert!(stats.guesses < 100, "{:?}", stats);
+        assert!(stats_1000.guesses >= 1000 - 2);
+    }
+
+    #[test]
+    fn remove_clue_matches_rebuild() {
+        let rng = &mut ChaChaRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let mut sudoku = Sudoku::generate_solved();
+            let mut solver = SudokuSolver::from_sudoku(sudoku).unwrap();
+            let mut cells: Vec<u8> = (0..81).collect();
+            rng.shuffle(&mut cells);
+
+            for cell in cells {
+                let num = sudoku.0[cell as usize];
+                sudoku.0[cell as usize] = 0;
+                solver.remove_clue(&sudoku, cell, num);
+
+                let rebuilt = SudokuSolver::from_sudoku(sudoku).unwrap();
+                assert_eq!(solver.poss_cells.0, rebuilt.poss_cells.0);
+                assert_eq!(solver.unsolved_cells.0, rebuilt.unsolved_cells.0);
+                assert_eq!(
+                    solver.solutions_count_up_to(2),
+                    sudoku.solutions_count_up_to(2),
+                    "{}",
+                    sudoku
+                );
+            }
+        }
+    }
+}
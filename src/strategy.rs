@@ -12,10 +12,18 @@
 //! slower than the fast solver.
 
 pub mod deduction;
+mod difficulty;
+mod hints;
+mod link_graph;
 mod solver;
 mod strategies;
 pub(crate) mod utils;
+mod workspace;
 
 pub use self::deduction::Deduction;
+pub use self::difficulty::{Difficulty, RemainingDifficulty, Tier};
+pub use self::hints::{ExclusionReason, SingleHint, SingleKind};
+pub use self::link_graph::{LinkGraph, StrongLink};
 pub use self::solver::StrategySolver;
-pub use self::strategies::Strategy;
+pub use self::strategies::{parse_strategy_list, Strategy};
+pub use self::workspace::Workspace;
@@ -1,9 +1,11 @@
 //! Results of strategy applications
 
-use super::Strategy;
+use super::{Difficulty, Strategy};
 use crate::bitset::Set;
 use crate::board::Candidate;
 use crate::board::*;
+use crate::helper::CellArray;
+use std::collections::BTreeMap;
 
 type DeductionRange = std::ops::Range<usize>;
 type _Deduction = Deduction<DeductionRange>;
@@ -57,11 +59,92 @@ impl Deductions {
             eliminated_entries: &self.eliminated_entries,
         }
     }
+
+    /// Returns a copy without the redundant deductions, i.e. those that only eliminate candidates which earlier
+    /// deductions already eliminated or ruled out by entering digits, and those that enter a digit in a cell that
+    /// was already filled by an earlier deduction. The remaining deductions are kept in order.
+    ///
+    /// Strategies that are applied exhaustively can find overlapping patterns in one go, e.g. a naked pair and
+    /// a naked triple containing it. This gives a solving path without such repetitions, e.g. for teaching.
+    pub fn canonicalize(&self) -> Deductions {
+        // candidates known to be false after the deductions kept so far
+        let mut impossible = CellArray([Set::NONE; 81]);
+        let mut filled = Set::NONE;
+        let mut deductions = vec![];
+        let mut eliminated_entries = vec![];
+
+        for deduction in &self.deductions {
+            if let Some(Candidate { cell, digit }) = deduction.placed_candidate() {
+                if filled.contains(cell) {
+                    continue;
+                }
+                filled |= cell;
+                impossible[cell] = !digit.as_set();
+                for peer in cell.peer_set() {
+                    impossible[peer] |= digit;
+                }
+                deductions.push(deduction.clone());
+                continue;
+            }
+
+            let conflicts = &self.eliminated_entries[deduction.conflicts_range()];
+            if conflicts
+                .iter()
+                .all(|candidate| impossible[candidate.cell].contains(candidate.digit))
+            {
+                continue;
+            }
+            let new_range = eliminated_entries.len()..eliminated_entries.len() + conflicts.len();
+            for candidate in conflicts {
+                impossible[candidate.cell] |= candidate.digit;
+            }
+            eliminated_entries.extend_from_slice(conflicts);
+            deductions.push(deduction.clone().map_conflicts(|_| new_range));
+        }
+
+        Deductions {
+            deductions,
+            deduced_entries: self.deduced_entries.clone(),
+            eliminated_entries,
+        }
+    }
+
+    /// Returns the [`Difficulty`] of the hardest strategy used in these deductions.
+    /// Without any deductions, this is `Difficulty::Easy`.
+    pub fn difficulty(&self) -> Difficulty {
+        self.iter()
+            .map(|deduction| deduction.strategy().difficulty())
+            .max()
+            .unwrap_or(Difficulty::Easy)
+    }
+
+    /// Returns the sum of the [`score_weight`](Strategy::score_weight)s of the strategies of all deductions.
+    /// Unlike the [`difficulty`](Self::difficulty), this also accounts for how often each strategy was needed,
+    /// so sudokus of the same difficulty can still be ordered.
+    pub fn score(&self) -> u32 {
+        self.iter()
+            .map(|deduction| deduction.strategy().score_weight())
+            .sum()
+    }
+
+    /// Counts how often each strategy was used in these deductions, e.g. for a custom score.
+    /// Strategies that weren't used have no entry.
+    pub fn strategy_counts(&self) -> BTreeMap<Strategy, usize> {
+        let mut counts = BTreeMap::new();
+        for deduction in self.iter() {
+            *counts.entry(deduction.strategy()).or_insert(0) += 1;
+        }
+        counts
+    }
 }
 
 /// Result of a single, successful strategy application
 ///
 /// This enum contains the data necessary to explain why the step could be taken.
+///
+/// Deductions obtained from [`Deductions`] implement `PartialEq`, `Eq` and `Hash`.
+/// Eliminated candidates are compared by value, so a deduction can be checked against
+/// one constructed by hand, e.g. in tests.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
@@ -103,12 +186,49 @@ pub enum Deduction<T> {
         positions: Set<Position<Line>>,
         conflicts: T,
     },
+    /// Result of [`FinnedXWing`](super::Strategy::FinnedXWing)
+    FinnedFish {
+        digit: Digit,
+        /// The base lines of the fish.
+        lines: Set<Line>,
+        /// The positions in the `lines` that make up the fish without its fins.
+        /// The number of positions is always equal to the number of lines.
+        positions: Set<Position<Line>>,
+        /// The candidates in the `lines` outside of `positions`. They are all in the same block
+        /// and only candidates in that block are eliminated.
+        fin_cells: Set<Cell>,
+        conflicts: T,
+    },
     Fish {
         digit: Digit,
         base: Set<House>,
         cover: Set<House>,
         conflicts: T,
     },
+    /// Result of [`Skyscraper`](super::Strategy::Skyscraper)
+    Skyscraper {
+        /// The 2 parallel lines in which `digit` has exactly 2 positions, one of which they share.
+        base_lines: Set<Line>,
+        digit: Digit,
+        conflicts: T,
+    },
+    /// Result of [`TwoStringKite`](super::Strategy::TwoStringKite)
+    TwoStringKite {
+        /// The row and column in which `digit` has exactly 2 positions, one end of each in the same block.
+        row: Row,
+        col: Col,
+        digit: Digit,
+        conflicts: T,
+    },
+    /// Result of [`EmptyRectangle`](super::Strategy::EmptyRectangle)
+    EmptyRectangle {
+        /// The block in which `digit` is confined to one row and one column.
+        block: Block,
+        digit: Digit,
+        /// The line in which `digit` has exactly 2 positions, one of them in the row or column.
+        conjugate_house: House,
+        conflicts: T,
+    },
     /// Result of [`XyWing`](super::Strategy::XyWing), [`XyzWing`](super::Strategy::XyzWing)
     Wing {
         hinge: Cell,
@@ -119,11 +239,75 @@ pub enum Deduction<T> {
         pincers: Set<Cell>,
         conflicts: T,
     },
+    /// Result of [`WWing`](super::Strategy::WWing)
+    WWing {
+        /// The two bivalue cells with the same candidates. They don't share a house.
+        cells: Set<Cell>,
+        /// The house in which the other candidate of `cells` can only be in two cells, each of which sees
+        /// one of `cells`.
+        connector_house: House,
+        /// The digit that one of `cells` must contain.
+        digit: Digit,
+        conflicts: T,
+    },
     AvoidableRectangle {
         /// The 2 rows and 2 columns forming the avoidable rectangle. The cells where they overlap always occupy 2 blocks in one chute.
         lines: Set<Line>,
         conflicts: T,
     },
+    /// Result of [`UniqueRectangles`](super::Strategy::UniqueRectangles)
+    UniqueRectangle {
+        /// The cells of the rectangle that contain only the two `digits`.
+        floor: Set<Cell>,
+        /// The other cells of the rectangle. Besides the two `digits`, one or both of them contain further
+        /// candidates one of which must be true, or else the sudoku would have a second solution.
+        roof: Set<Cell>,
+        /// The two digits that would form the deadly pattern. The 4 cells occupy exactly 2 blocks.
+        digits: Set<Digit>,
+        conflicts: T,
+    },
+    /// Result of [`Bug`](super::Strategy::Bug)
+    Bug {
+        /// The only unsolved cell with 3 candidates. All others have 2.
+        cell: Cell,
+        /// The digit that `cell` must contain. The other 2 candidates are eliminated from it.
+        digit: Digit,
+        conflicts: T,
+    },
+    /// Result of [`RemotePairs`](super::Strategy::RemotePairs)
+    RemotePairs {
+        /// The bivalue cells forming the chain. Cells that see each other contain different `digits`.
+        cells: Set<Cell>,
+        /// The 2 candidates of all `cells`.
+        digits: Set<Digit>,
+        conflicts: T,
+    },
+    /// Result of [`XyChain`](super::Strategy::XyChain)
+    XyChain {
+        /// The bivalue cells forming the chain. Each cell sees the next one in the chain and shares a digit with it.
+        cells: Set<Cell>,
+        /// The first and last cell of the chain. One of them must contain `digit`.
+        ends: Set<Cell>,
+        digit: Digit,
+        conflicts: T,
+    },
+    /// Result of [`XChain`](super::Strategy::XChain)
+    XChain {
+        digit: Digit,
+        /// The cells of the chain, which are alternately connected by strong and weak links on `digit`.
+        cells: Set<Cell>,
+        conflicts: T,
+    },
+    /// Result of [`GroupedXChain`](super::Strategy::GroupedXChain)
+    GroupedChain {
+        digit: Digit,
+        /// The cells of all nodes of the chain.
+        cells: Set<Cell>,
+        /// The first and last node of the chain. Each node is a single cell or several cells of a miniline.
+        /// One of them must contain `digit`.
+        ends: [Set<Cell>; 2],
+        conflicts: T,
+    },
     //SinglesChain(T),
 }
 
@@ -141,6 +325,10 @@ impl Deduction<&'_ [Candidate]> {
                 4 => Strategy::Jellyfish,
                 _ => unreachable!(),
             },
+            FinnedFish { lines, .. } => match lines.len() {
+                2 => Strategy::FinnedXWing,
+                _ => unreachable!(),
+            },
             //SinglesChain { .. } => Strategy::SinglesChain,
             Subsets {
                 house,
@@ -186,57 +374,95 @@ impl Deduction<&'_ [Candidate]> {
                     _ => unreachable!(),
                 }
             }
+            Skyscraper { .. } => Strategy::Skyscraper,
+            TwoStringKite { .. } => Strategy::TwoStringKite,
+            EmptyRectangle { .. } => Strategy::EmptyRectangle,
             Wing { hinge_digits, .. } => match hinge_digits.len() {
                 2 => Strategy::XyWing,
                 3 => Strategy::XyzWing,
                 _ => unreachable!(),
             },
+            WWing { .. } => Strategy::WWing,
             AvoidableRectangle { .. } => unimplemented!(),
+            UniqueRectangle { .. } => Strategy::UniqueRectangles,
+            Bug { .. } => Strategy::Bug,
+            RemotePairs { .. } => Strategy::RemotePairs,
+            XyChain { .. } => Strategy::XyChain,
+            XChain { .. } => Strategy::XChain,
+            GroupedChain { .. } => Strategy::GroupedXChain,
         }
     }
 }
 
-#[rustfmt::skip]
-impl _Deduction {
-    /// Replace the index ranges from the internal representation with slices
-    /// for the external API
-    fn with_slices(self, eliminated: &[Candidate]) -> Deduction<&[Candidate]> {
+impl<T> Deduction<T> {
+    /// Converts the eliminated candidates with `f`, leaving everything else unchanged.
+    /// For example, `deduction.map_conflicts(<[Candidate]>::to_vec)` turns a borrowed deduction into
+    /// an owned one, which can be stored and later replayed with
+    /// [`StrategySolver::apply_deduction`](super::StrategySolver::apply_deduction).
+    #[rustfmt::skip]
+    pub fn map_conflicts<U>(self, f: impl FnOnce(T) -> U) -> Deduction<U> {
         use self::Deduction::*;
         match self {
             NakedSingles(c) => NakedSingles(c),
             HiddenSingles(c, h) => HiddenSingles(c, h),
+            LockedCandidates { miniline, digit, is_pointing, conflicts }
+            => LockedCandidates { miniline, digit, is_pointing, conflicts: f(conflicts) },
+            Subsets { house, positions, digits, conflicts }
+            => Subsets { house, positions, digits, conflicts: f(conflicts) },
+            BasicFish { lines, positions, digit, conflicts }
+            => BasicFish { lines, positions, digit, conflicts: f(conflicts) },
+            FinnedFish { lines, positions, digit, fin_cells, conflicts }
+            => FinnedFish { lines, positions, digit, fin_cells, conflicts: f(conflicts) },
+            Fish { digit, base, cover, conflicts }
+            => Fish { digit, base, cover, conflicts: f(conflicts) },
+            Skyscraper { base_lines, digit, conflicts }
+            => Skyscraper { base_lines, digit, conflicts: f(conflicts) },
+            TwoStringKite { row, col, digit, conflicts }
+            => TwoStringKite { row, col, digit, conflicts: f(conflicts) },
+            EmptyRectangle { block, digit, conjugate_house, conflicts }
+            => EmptyRectangle { block, digit, conjugate_house, conflicts: f(conflicts) },
+            Wing { hinge, hinge_digits, pincers, conflicts }
+            => Wing { hinge, hinge_digits, pincers, conflicts: f(conflicts) },
+            WWing { cells, connector_house, digit, conflicts }
+            => WWing { cells, connector_house, digit, conflicts: f(conflicts) },
+            AvoidableRectangle { lines, conflicts }
+            => AvoidableRectangle { lines, conflicts: f(conflicts) },
+            UniqueRectangle { floor, roof, digits, conflicts }
+            => UniqueRectangle { floor, roof, digits, conflicts: f(conflicts) },
+            Bug { cell, digit, conflicts }
+            => Bug { cell, digit, conflicts: f(conflicts) },
+            RemotePairs { cells, digits, conflicts }
+            => RemotePairs { cells, digits, conflicts: f(conflicts) },
+            XyChain { cells, ends, digit, conflicts }
+            => XyChain { cells, ends, digit, conflicts: f(conflicts) },
+            XChain { digit, cells, conflicts }
+            => XChain { digit, cells, conflicts: f(conflicts) },
+            GroupedChain { digit, cells, ends, conflicts }
+            => GroupedChain { digit, cells, ends, conflicts: f(conflicts) },
+        }
+    }
 
-            LockedCandidates {
-                miniline, digit, is_pointing,
-                conflicts
-            } => LockedCandidates { miniline, digit, is_pointing, conflicts: &eliminated[conflicts] },
-
-            Subsets {
-                house, positions, digits,
-                conflicts
-            }
-            => Subsets { house, positions, digits, conflicts: &eliminated[conflicts]},
-
-            BasicFish {
-                lines, positions, digit,
-                conflicts
-            }
-            => BasicFish { lines, positions, digit, conflicts: &eliminated[conflicts]},
-
-            Fish {
-                digit, base, cover,
-                conflicts,
-            }
-            => Fish { digit, base, cover, conflicts: &eliminated[conflicts] },
+    /// Returns the candidate entered by this deduction, if it enters one.
+    pub(crate) fn placed_candidate(&self) -> Option<Candidate> {
+        match *self {
+            Deduction::NakedSingles(candidate) | Deduction::HiddenSingles(candidate, _) => Some(candidate),
+            _ => None,
+        }
+    }
+}
 
-            Wing {
-                hinge, hinge_digits, pincers,
-                conflicts
-            }
-            => Wing { hinge, hinge_digits, pincers, conflicts: &eliminated[conflicts] },
+impl _Deduction {
+    /// Replace the index ranges from the internal representation with slices
+    /// for the external API
+    pub(crate) fn with_slices(self, eliminated: &[Candidate]) -> Deduction<&[Candidate]> {
+        self.map_conflicts(|conflicts| &eliminated[conflicts])
+    }
 
-            AvoidableRectangle { .. } => unimplemented!(),
-            //SinglesChain(x) => SinglesChain(&eliminated[x]),
-        }
+    /// Returns the range of candidates in the eliminated entries that this deduction removed.
+    /// Empty for deductions that enter a digit.
+    pub(crate) fn conflicts_range(&self) -> DeductionRange {
+        let mut range = 0..0;
+        self.clone().map_conflicts(|conflicts| range = conflicts);
+        range
     }
 }
@@ -0,0 +1,89 @@
+//! Coarse difficulty grades for sudokus and tiers of strategies
+
+use super::Strategy;
+
+/// The difficulty of a sudoku, as judged by the hardest [`Strategy`](Strategy) required to solve it.
+/// See [`Strategy::difficulty`](Strategy::difficulty) for the grade of each strategy.
+///
+/// Grades are ordered from easiest to hardest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Solvable with naked and hidden singles alone.
+    Easy,
+    /// Requires locked candidates or naked and hidden subsets.
+    Medium,
+    /// Requires basic or finned fish, skyscrapers, two-string kites or empty rectangles.
+    Hard,
+    /// Requires wings or chains. Uniqueness based strategies are graded as unfair as well,
+    /// but [`Sudoku::grade`](crate::Sudoku::grade) doesn't use them.
+    Unfair,
+    /// Requires mutant fish.
+    Extreme,
+}
+
+/// What is still needed to solve a sudoku after the strategies of a
+/// [`StrategySolver`](super::StrategySolver) got stuck.
+/// See [`StrategySolver::remaining_difficulty`](super::StrategySolver::remaining_difficulty).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemainingDifficulty {
+    /// The sudoku has a unique solution, but the strategies are too weak to find it.
+    /// It can be solved with stronger strategies or by guessing.
+    NeedsGuessing,
+    /// The sudoku has multiple solutions, so it can only be finished by guessing.
+    NotUnique,
+    /// The entered digits and remaining candidates contradict each other, the sudoku has no solution.
+    Contradiction,
+}
+
+/// A group of related [`Strategy`]s of similar complexity, e.g. for unlocking techniques
+/// step by step in a game. See [`Strategy::tier`] for the tier of each strategy.
+///
+/// Unlike [`Difficulty`], which grades sudokus by the hardest strategy needed, tiers group
+/// strategies by technique. Tiers are ordered from simplest to most complex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tier {
+    /// Singles and locked candidates.
+    Basic,
+    /// Naked and hidden subsets.
+    Intermediate,
+    /// Basic fish up to swordfish and wings.
+    Advanced,
+    /// Jellyfish, finned and mutant fish, chains and uniqueness based strategies.
+    Expert,
+}
+
+impl Tier {
+    /// Returns all strategies in this tier. Strategies that aren't implemented yet,
+    /// like [`AvoidableRectangles`](Strategy::AvoidableRectangles), are left out.
+    ///
+    /// [`UniqueRectangles`](Strategy::UniqueRectangles) and [`Bug`](Strategy::Bug) are left out as well.
+    /// They are only valid for sudokus with a unique solution, so they must be added to the list explicitly.
+    pub fn strategies(self) -> &'static [Strategy] {
+        use self::Strategy::*;
+        match self {
+            Tier::Basic => &[NakedSingles, HiddenSingles, LockedCandidates],
+            Tier::Intermediate => &[
+                NakedPairs,
+                HiddenPairs,
+                NakedTriples,
+                HiddenTriples,
+                NakedQuads,
+                HiddenQuads,
+            ],
+            Tier::Advanced => &[XWing, Swordfish, XyWing, XyzWing, WWing],
+            Tier::Expert => &[
+                Jellyfish,
+                Skyscraper,
+                TwoStringKite,
+                EmptyRectangle,
+                FinnedXWing,
+                MutantSwordfish,
+                MutantJellyfish,
+                RemotePairs,
+                XyChain,
+                XChain,
+                GroupedXChain,
+            ],
+        }
+    }
+}
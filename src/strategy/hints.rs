@@ -0,0 +1,41 @@
+//! Justified hints for beginners
+
+use crate::board::*;
+
+/// A digit that can be entered in a cell because it is a naked or hidden single,
+/// together with the reason. See [`StrategySolver::single_hints`](super::StrategySolver::single_hints).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SingleHint {
+    /// The cell in which the digit can be entered.
+    pub cell: Cell,
+    /// The digit that can be entered.
+    pub digit: Digit,
+    /// Whether the digit is the last candidate of the cell or the last position in a house.
+    pub kind: SingleKind,
+    /// The house in which `cell` is the only possible position for `digit`.
+    /// `None` for naked singles.
+    pub house: Option<HouseType>,
+}
+
+/// The kind of a [`SingleHint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SingleKind {
+    /// `digit` is the only candidate left in `cell`.
+    Naked,
+    /// `cell` is the only position left for `digit` in `house`.
+    Hidden,
+}
+
+/// The reason a digit can not be entered in a cell.
+/// See [`StrategySolver::cell_exclusions`](super::StrategySolver::cell_exclusions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExclusionReason {
+    /// The digit is already entered in this cell, which shares a house with the excluded cell.
+    Peer(Cell),
+    /// The candidate was eliminated by the deduction with this index in
+    /// [`StrategySolver::deductions`](super::StrategySolver::deductions).
+    Deduction(usize),
+    /// The candidate was eliminated from outside of the solver, e.g. with
+    /// [`StrategySolver::forbid`](super::StrategySolver::forbid) or in the grid state it was created from.
+    External,
+}
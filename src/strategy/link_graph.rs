@@ -0,0 +1,80 @@
+//! Graph of strong links for a single digit
+
+use crate::bitset::Set;
+use crate::board::*;
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::fmt::Write;
+
+/// The strong links of one digit in the current state of a [`StrategySolver`](super::StrategySolver).
+///
+/// The nodes are all cells that still have the digit as a candidate. Two cells are connected by an edge,
+/// if they are the only two possible positions for the digit in some house (a conjugate pair).
+/// If one of them does not contain the digit, the other one must. These are the links that
+/// chaining strategies like coloring follow.
+///
+/// With the `serde` feature, the graph can be serialized. Cells and houses are serialized as their indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkGraph {
+    /// The digit whose links are contained in this graph.
+    pub digit: Digit,
+    /// All cells in which `digit` is a candidate.
+    pub nodes: Set<Cell>,
+    /// All strong links between the `nodes`, ordered by house.
+    pub edges: Vec<StrongLink>,
+}
+
+/// A pair of cells that are the only possible positions for a digit in a house.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StrongLink {
+    /// The house in which the link exists.
+    pub house: House,
+    /// The two cells, ordered by index.
+    pub cells: [Cell; 2],
+}
+
+impl LinkGraph {
+    /// Returns the graph in the DOT language of [Graphviz](https://graphviz.org/) for visualization.
+    /// Nodes are named by row and column of the cell (`r1c1` to `r9c9`) and edges are labeled by the house.
+    pub fn to_dot(&self) -> String {
+        let cell_name = |cell: Cell| format!("r{}c{}", cell.row().get() + 1, cell.col().get() + 1);
+
+        let mut dot = format!("graph digit_{} {{\n", self.digit.get());
+        for cell in self.nodes {
+            writeln!(dot, "    {};", cell_name(cell)).unwrap();
+        }
+        for link in &self.edges {
+            writeln!(
+                dot,
+                "    {} -- {} [label=\"{}\"];",
+                cell_name(link.cells[0]),
+                cell_name(link.cells[1]),
+                link.house.categorize()
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for LinkGraph {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let nodes = self.nodes.into_iter().map(Cell::get).collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|link| (link.house.get(), link.cells[0].get(), link.cells[1].get()))
+            .collect::<Vec<_>>();
+
+        let mut graph = serializer.serialize_struct("LinkGraph", 3)?;
+        graph.serialize_field("digit", &self.digit.get())?;
+        graph.serialize_field("nodes", &nodes)?;
+        graph.serialize_field("edges", &edges)?;
+        graph.end()
+    }
+}
@@ -4,11 +4,19 @@ use crate::board::*;
 use crate::helper::{CellArray, DigitArray, HouseArray, Unsolvable};
 use crate::strategy::{
     deduction::{Deduction, Deductions},
+    difficulty::RemainingDifficulty,
+    hints::{ExclusionReason, SingleHint, SingleKind},
+    link_graph::{LinkGraph, StrongLink},
     strategies::*,
 };
-use crate::Sudoku;
+use crate::{Sudoku, Uniqueness};
+use std::collections::BTreeMap;
 
 type EliminationsRange = std::ops::Range<usize>;
+
+// Number of new entries from which on the possible positions of digits in houses are recomputed
+// instead of updated one entry at a time. Below ~10 entries, the update is faster.
+const HOUSE_POSS_POSITIONS_RECOMPUTE_THRESHOLD: usize = 10;
 type _Deduction = Deduction<EliminationsRange>;
 
 /// The `StrategySolver` is the struct for solving sudokus with
@@ -17,7 +25,7 @@ type _Deduction = Deduction<EliminationsRange>;
 /// It is built from a single `Sudoku` for which it stores the current
 /// state and the history of applied strategies. It can find hints
 /// or solve the `Sudoku` completely and return the solution path.
-/// From the solving path, the difficulty can be graded.
+/// From the solving path, the difficulty can be graded, see [`Deductions::difficulty`].
 
 // To allow for the above functionality, this struct contains caches
 // of various properties of the sudoku grid. The caches are lazily updated
@@ -43,6 +51,9 @@ pub struct StrategySolver {
 
     // optimization hints for strategies
     pub(crate) hidden_singles_last_house: u8,
+    // if set, naked singles are never entered while updating the candidates
+    // and only found through the NakedSingles strategy, one deduction per cell
+    pub(crate) explicit_singles: bool,
 
     // The initial state of a sudoku given as a puzzle.
     // If the solution is unique, this can be used for the strategy of
@@ -71,6 +82,7 @@ impl StrategySolver {
             eliminated_entries: vec![],
             n_solved: 0,
             hidden_singles_last_house: 0,
+            explicit_singles: false,
             clues: None,
             grid: State::from(Sudoku([0; 81])),
             cell_poss_digits: State::from(CellArray([Set::ALL; 81])),
@@ -81,11 +93,7 @@ impl StrategySolver {
 
     /// Construct a new StrategySolver
     pub fn from_sudoku(sudoku: Sudoku) -> StrategySolver {
-        let deduced_entries = sudoku
-            .iter()
-            .enumerate()
-            .filter_map(|(cell, opt_num)| opt_num.map(|digit| Candidate::new(cell as u8, digit)))
-            .collect();
+        let deduced_entries = sudoku.clues().collect();
 
         StrategySolver {
             deduced_entries,
@@ -128,6 +136,19 @@ impl StrategySolver {
         }
     }
 
+    /// Disable or enable entering naked singles while the candidates are updated.
+    ///
+    /// By default, [`NakedSingles`](super::strategies::Strategy::NakedSingles) places chains of
+    /// singles as a side effect of removing candidates, when it's the first strategy in a solve.
+    /// With `explicit_singles` set, every entry is made by a single step of
+    /// [`NakedSingles`](super::strategies::Strategy::NakedSingles) or
+    /// [`HiddenSingles`](super::strategies::Strategy::HiddenSingles) and recorded as its own deduction.
+    /// This is slower, but useful for step-by-step solving.
+    pub fn explicit_singles(mut self, explicit_singles: bool) -> StrategySolver {
+        self.explicit_singles = explicit_singles;
+        self
+    }
+
     /// Construct a new StrategySolver from a printout of cell candidates.
     /// This allows communicating the impossibility of some candidates, that aren't already
     /// trivially conflicting with entries.
@@ -164,19 +185,42 @@ impl StrategySolver {
     }
 
     /// Returns the current state of the Sudoku including potential candidates
+    ///
+    /// This is cheap, if nothing changed since the solver last updated its internal state.
+    /// The public methods that enter digits or remove candidates, like [`insert_candidate`](Self::insert_candidate)
+    /// or [`solve_in_place`](Self::solve_in_place), update it before they return, so repeated calls in between
+    /// don't have to recompute anything. Otherwise, the solver has to be cloned to bring the state up to date.
     pub fn grid_state(&self) -> [CellState; 81] {
-        // cloning so .update_grid() can be called which updates caches
+        if self.grid_state_caches_up_to_date() {
+            return self.grid_state_from_caches();
+        }
+        // cloning so the caches can be updated
         let mut solver = self.clone();
+        solver.update_grid_state_caches();
+        solver.grid_state_from_caches()
+    }
 
-        let mut grid = [CellState::Candidates(Set::NONE); 81];
+    // Whether all entries and eliminations have been applied to the grid and the cell candidates
+    fn grid_state_caches_up_to_date(&self) -> bool {
+        let n_deduced = self.deduced_entries.len();
+        self.grid.next_deduced as usize == n_deduced
+            && self.cell_poss_digits.next_deduced as usize == n_deduced
+            && self.cell_poss_digits.last_eliminated as usize == self.eliminated_entries.len()
+    }
+
+    // Bring the caches needed for `grid_state` up to date, so it doesn't have to clone the solver.
+    pub(crate) fn update_grid_state_caches(&mut self) {
+        self.update_grid();
+        let _ = self._update_cell_poss_house_solved(false, false);
+    }
 
-        solver.update_grid();
-        let _ = solver._update_cell_poss_house_solved(false, false);
+    fn grid_state_from_caches(&self) -> [CellState; 81] {
+        let mut grid = [CellState::Candidates(Set::NONE); 81];
 
-        for (cell, &digits) in solver.cell_poss_digits.state.iter().enumerate() {
+        for (cell, &digits) in self.cell_poss_digits.state.iter().enumerate() {
             grid[cell] = CellState::Candidates(digits);
         }
-        for (cell, &digit) in solver
+        for (cell, &digit) in self
             .grid
             .state
             .0
@@ -189,6 +233,74 @@ impl StrategySolver {
         grid
     }
 
+    /// Returns a grid of the current state in which each unsolved cell is drawn as a 3x3 grid
+    /// of its candidates. Each candidate is at the position it has on a keypad starting with 1 in the top left
+    /// and missing candidates are drawn as `.`. Solved cells show their digit in the center.
+    ///
+    /// ```text
+    /// ┌─────────────┬─────────────┬─────────────┐
+    /// │ ... ...     │ ...     1.. │     ... ... │
+    /// │ 45. 45.  3  │ 4..  2  4.. │  6  .5. .5. │
+    /// │ ... 78.     │ ..9     7.. │     789 7.. │
+    /// │             │             │             │
+    /// │     .2. ... │     ...     │ ... .2.     │
+    /// ...
+    /// ```
+    pub fn display_candidates(&self) -> String {
+        let mut grid = String::new();
+        print_candidate_grid(
+            &mut grid,
+            self.grid_state(),
+            "┌",
+            "┐",
+            "└",
+            "┘",
+            "├",
+            "┤",
+            "┬",
+            "┴",
+            "┼",
+            "─",
+            "│",
+        )
+        .expect("a Display implementation returned an error unexpectedly");
+        grid
+    }
+
+    /// Like [`display_candidates`](Self::display_candidates), but draws the grid with the ASCII characters
+    /// `+`, `-` and `|`, e.g. for terminals or logs without unicode support.
+    /// Every line is 43 characters wide, so the output is suitable for snapshot tests.
+    ///
+    /// ```text
+    /// +-------------+-------------+-------------+
+    /// | ... ...     | ...     1.. |     ... ... |
+    /// | 45. 45.  3  | 4..  2  4.. |  6  .5. .5. |
+    /// | ... 78.     | ..9     7.. |     789 7.. |
+    /// |             |             |             |
+    /// |     .2. ... |     ...     | ... .2.     |
+    /// ...
+    /// ```
+    pub fn candidate_grid_string(&self) -> String {
+        let mut grid = String::new();
+        print_candidate_grid(
+            &mut grid,
+            self.grid_state(),
+            "+",
+            "+",
+            "+",
+            "+",
+            "+",
+            "+",
+            "+",
+            "+",
+            "+",
+            "-",
+            "|",
+        )
+        .expect("a Display implementation returned an error unexpectedly");
+        grid
+    }
+
     /// Returns the current state of the given `cell`
     pub fn cell_state(&mut self, cell: Cell) -> CellState {
         self.update_grid();
@@ -203,9 +315,10 @@ impl StrategySolver {
         }
     }
 
-    /// Try to insert the given candidate. Fails, if the cell already contains a digit.
+    /// Try to insert the given candidate. Fails, if the cell already contains a different digit.
     pub fn insert_candidate(&mut self, candidate: Candidate) -> Result<(), ()> {
         self.update_grid();
+        let n_deductions = self.deductions.len();
         Self::push_new_candidate(
             &mut self.grid.state,
             &mut self.deduced_entries,
@@ -215,11 +328,363 @@ impl StrategySolver {
         )
         .map_err(|Unsolvable| ())?;
         // TODO: remove the initial strategy insertion
-        self.deductions.pop();
+        self.deductions.truncate(n_deductions);
+        self.update_grid_state_caches();
+
+        Ok(())
+    }
+
+    /// Eliminate all `digits` from all `cells`, e.g. to add the constraints of a sudoku variant.
+    /// The eliminations are taken as given and not recorded as deductions.
+    ///
+    /// Returns [`Unsolvable`] and does nothing, if one of the cells already contains one of the `digits`
+    /// or would be left without any candidates.
+    pub fn forbid(&mut self, cells: Set<Cell>, digits: Set<Digit>) -> Result<(), Unsolvable> {
+        self.update_grid();
+        let _ = self._update_cell_poss_house_solved(false, false);
+
+        let mut eliminated = vec![];
+        for cell in cells {
+            let cell_digits = match Digit::new_checked(self.grid.state.0[cell.as_index()]) {
+                Some(digit) => digit.as_set(),
+                None => self.cell_poss_digits.state[cell],
+            };
+            if cell_digits.without(digits).is_empty() {
+                return Err(Unsolvable);
+            }
+            eliminated.extend(
+                (cell_digits & digits)
+                    .into_iter()
+                    .map(|digit| Candidate { cell, digit }),
+            );
+        }
+        self.eliminated_entries.extend(eliminated);
+        self.update_grid_state_caches();
+        Ok(())
+    }
+
+    /// Applies a deduction that was made elsewhere, e.g. to replay a solution path recorded by another solver.
+    /// Owned deductions can be obtained with [`Deduction::map_conflicts`]. The deduction is recorded as if
+    /// this solver had made it.
+    ///
+    /// Only the consistency with the current state is checked, not the reasoning behind the deduction.
+    /// Returns [`Unsolvable`] and does nothing, if the entered digit is not a candidate of its cell,
+    /// an eliminated candidate is in a solved cell or a cell would be left without candidates.
+    pub fn apply_deduction(&mut self, deduction: Deduction<Vec<Candidate>>) -> Result<(), Unsolvable> {
+        self.update_grid();
+        let _ = self._update_cell_poss_house_solved(false, false);
+
+        let is_unsolved = |cell: Cell| self.grid.state.0[cell.as_index()] == 0;
+        let placed = deduction.placed_candidate();
+        if let Some(Candidate { cell, digit }) = placed {
+            if !is_unsolved(cell) || !self.cell_poss_digits.state[cell].contains(digit) {
+                return Err(Unsolvable);
+            }
+        }
+
+        let mut conflicts = vec![];
+        let deduction = deduction.map_conflicts(|candidates| conflicts = candidates);
+        let mut remaining_digits = self.cell_poss_digits.state;
+        for &Candidate { cell, digit } in &conflicts {
+            remaining_digits[cell].remove(digit.as_set());
+            if !is_unsolved(cell) || remaining_digits[cell].is_empty() {
+                return Err(Unsolvable);
+            }
+        }
 
+        let n_eliminated = self.eliminated_entries.len();
+        self.eliminated_entries.extend(conflicts);
+        let deduction = deduction.map_conflicts(|()| n_eliminated..self.eliminated_entries.len());
+        match placed {
+            Some(candidate) => {
+                let _ = Self::push_new_candidate(
+                    &mut self.grid.state,
+                    &mut self.deduced_entries,
+                    candidate,
+                    &mut self.deductions,
+                    deduction,
+                );
+            }
+            None => self.deductions.push(deduction),
+        }
+        // update again so the new entries are inserted
+        self.update_grid_state_caches();
         Ok(())
     }
 
+    /// Insert the given candidate like [`insert_candidate`](Self::insert_candidate) and enter all naked and
+    /// hidden singles that follow from it, until no more are found. Returns the singles that were entered.
+    ///
+    /// Returns `None`, if the cell already contains a different digit or if the propagation runs into
+    /// a contradiction. The singles found up to that point remain entered in the latter case.
+    pub fn insert_candidate_and_propagate(&mut self, candidate: Candidate) -> Option<Deductions> {
+        self.insert_candidate(candidate).ok()?;
+        let n_deductions = self.deductions.len();
+        let n_deduced = self.deduced_entries.len();
+
+        let singles = [Strategy::NakedSingles, Strategy::HiddenSingles];
+        'propagate: loop {
+            for strategy in &singles {
+                let n_deduced = self.deduced_entries.len();
+                strategy.deduce_one(self).ok()?;
+                if self.deduced_entries.len() > n_deduced {
+                    continue 'propagate;
+                }
+            }
+            break;
+        }
+        self.update_grid_state_caches();
+
+        Some(Deductions {
+            deductions: self.deductions[n_deductions..].to_vec(),
+            deduced_entries: self.deduced_entries[n_deduced..].to_vec(),
+            eliminated_entries: vec![],
+        })
+    }
+
+    /// Returns the total number of candidates in all unsolved cells.
+    /// It decreases with every deduction and is 0 once the sudoku is solved.
+    pub fn remaining_candidates_count(&mut self) -> usize {
+        self.update_grid();
+        let _ = self._update_cell_poss_house_solved(false, false);
+
+        Cell::all()
+            .filter(|cell| self.grid.state.0[cell.as_index()] == 0)
+            .map(|cell| self.cell_poss_digits.state[cell].len())
+            .sum()
+    }
+
+    /// Returns all naked and hidden singles that can be entered in the current state, without entering them.
+    /// Each candidate is listed once, sorted by cell, even if it is a single for multiple reasons.
+    ///
+    /// Returns [`Unsolvable`], if the sudoku is found to be impossible.
+    pub fn obvious_singles(&mut self) -> Result<Vec<Candidate>, Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let house_solved_digits = &self.house_solved_digits.state;
+
+        let mut singles = vec![];
+        naked_singles::find_naked_singles(cell_poss_digits, false, |candidate| {
+            singles.push(candidate);
+            Ok(())
+        })?;
+        hidden_singles::find_hidden_singles(
+            &mut 0,
+            cell_poss_digits,
+            house_solved_digits,
+            false,
+            |candidate, _| {
+                singles.push(candidate);
+                Ok(())
+            },
+        )?;
+
+        singles.sort();
+        singles.dedup();
+        Ok(singles)
+    }
+
+    /// Returns all naked and hidden singles in the current state together with the reason
+    /// they can be entered, without entering them. This is enough to explain a move like
+    /// "In box 3, 7 can only go in r2c8".
+    ///
+    /// Naked singles are listed first, sorted by cell, followed by the hidden singles, sorted by house.
+    /// A candidate that is a single for multiple reasons is listed once for each reason.
+    ///
+    /// Returns [`Unsolvable`], if the sudoku is found to be impossible.
+    pub fn single_hints(&mut self) -> Result<Vec<SingleHint>, Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let house_solved_digits = &self.house_solved_digits.state;
+
+        let mut hints = vec![];
+        naked_singles::find_naked_singles(cell_poss_digits, false, |Candidate { cell, digit }| {
+            hints.push(SingleHint {
+                cell,
+                digit,
+                kind: SingleKind::Naked,
+                house: None,
+            });
+            Ok(())
+        })?;
+        hidden_singles::find_hidden_singles(
+            &mut 0,
+            cell_poss_digits,
+            house_solved_digits,
+            false,
+            |Candidate { cell, digit }, house| {
+                hints.push(SingleHint {
+                    cell,
+                    digit,
+                    kind: SingleKind::Hidden,
+                    house: Some(house.categorize()),
+                });
+                Ok(())
+            },
+        )?;
+
+        Ok(hints)
+    }
+
+    /// Returns the digits that can not be entered in `cell` anymore, in ascending order, together with the reason.
+    /// If the digit is entered in a peer, that peer is named, even if the candidate was also eliminated
+    /// by a deduction. Returns an empty list, if `cell` is already solved.
+    ///
+    /// ```
+    /// use sudoku::Sudoku;
+    /// use sudoku::board::{Cell, Digit};
+    /// use sudoku::strategy::{ExclusionReason, StrategySolver};
+    ///
+    /// let sudoku = Sudoku::from_str_line(
+    ///     "12...............................................................................",
+    /// ).unwrap();
+    /// let mut solver = StrategySolver::from_sudoku(sudoku);
+    /// let exclusions = solver.cell_exclusions(Cell::new(2));
+    /// assert_eq!(exclusions, vec![
+    ///     (Digit::new(1), ExclusionReason::Peer(Cell::new(0))),
+    ///     (Digit::new(2), ExclusionReason::Peer(Cell::new(1))),
+    /// ]);
+    /// ```
+    pub fn cell_exclusions(&mut self, cell: Cell) -> Vec<(Digit, ExclusionReason)> {
+        self.update_grid();
+        let _ = self._update_cell_poss_house_solved(false, false);
+        let grid = &self.grid.state;
+        if grid.0[cell.as_index()] != 0 {
+            return vec![];
+        }
+
+        (!self.cell_poss_digits.state[cell])
+            .into_iter()
+            .map(|digit| {
+                let peer = cell
+                    .neighbors()
+                    .into_iter()
+                    .find(|peer| grid.0[peer.as_index()] == digit.get());
+                if let Some(peer) = peer {
+                    return (digit, ExclusionReason::Peer(peer));
+                }
+
+                let candidate = Candidate { cell, digit };
+                let reason = self
+                    .eliminated_entries
+                    .iter()
+                    .position(|&eliminated| eliminated == candidate)
+                    .and_then(|index| {
+                        self.deductions
+                            .iter()
+                            .position(|deduction| deduction.conflicts_range().contains(&index))
+                    })
+                    .map_or(ExclusionReason::External, ExclusionReason::Deduction);
+                (digit, reason)
+            })
+            .collect()
+    }
+
+    /// Returns the graph of strong links for `digit` in the current state, e.g. for visualization.
+    /// See [`LinkGraph`] for details.
+    ///
+    /// Returns [`Unsolvable`], if the sudoku is found to be impossible.
+    pub fn link_graph(&mut self, digit: Digit) -> Result<LinkGraph, Unsolvable> {
+        let edges = self
+            .conjugate_pairs(digit)?
+            .into_iter()
+            .map(|(house, cell1, cell2)| StrongLink {
+                house,
+                cells: [cell1, cell2],
+            })
+            .collect();
+
+        let house_poss_positions = &self.house_poss_positions.state;
+        let nodes = House::all()
+            .map(|house| house.cells_at(house_poss_positions[house][digit]))
+            .fold(Set::NONE, std::ops::BitOr::bitor);
+
+        Ok(LinkGraph { digit, nodes, edges })
+    }
+
+    /// Returns all houses in which `digit` has exactly two possible positions (conjugate pairs)
+    /// together with the two cells, ordered by house and then by cell.
+    /// In each pair, one of the cells must contain the digit.
+    ///
+    /// Returns [`Unsolvable`], if the sudoku is found to be impossible.
+    pub fn conjugate_pairs(&mut self, digit: Digit) -> Result<Vec<(House, Cell, Cell)>, Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        self.update_house_poss_positions()?;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        Ok(House::all()
+            .filter(|&house| house_poss_positions[house][digit].len() == 2)
+            .map(|house| {
+                let mut positions = house_poss_positions[house][digit].into_iter();
+                let mut cell = || house.cell_at(positions.next().unwrap());
+                (house, cell(), cell())
+            })
+            .collect())
+    }
+
+    /// Returns all houses whose remaining digits are all determined, but not entered yet: Every unsolved cell
+    /// in the house has only one candidate left and every missing digit has only one position left.
+    /// Houses in which all digits are entered already are not included.
+    ///
+    /// Returns [`Unsolvable`], if the sudoku is found to be impossible.
+    pub fn determined_houses(&mut self) -> Result<Vec<House>, Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        self.update_house_poss_positions()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let house_solved_digits = &self.house_solved_digits.state;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        Ok(House::all()
+            .filter(|&house| {
+                let missing_digits = Set::ALL.without(house_solved_digits[house]);
+                !missing_digits.is_empty()
+                    && missing_digits
+                        .into_iter()
+                        .all(|digit| house_poss_positions[house][digit].len() == 1)
+                    && house
+                        .cells()
+                        .into_iter()
+                        .map(|cell| cell_poss_digits[cell])
+                        .filter(|digits| !digits.is_empty())
+                        .all(|digits| digits.len() == 1)
+            })
+            .collect())
+    }
+
+    /// Returns all naked and hidden subsets with `subset_size` cells or digits in `house`, that eliminate
+    /// candidates. Sizes of 2 to 4 correspond to pairs, triples and quads.
+    /// The deductions are only listed, not applied.
+    ///
+    /// # Panics
+    /// Panics, if `subset_size` is not in `2..=4`.
+    pub fn subsets_in_house(&mut self, house: House, subset_size: usize) -> Deductions {
+        assert!(
+            (2..=4).contains(&subset_size),
+            "invalid subset size: {}",
+            subset_size
+        );
+        // Both searches need to start from the current state. If they ran on the same solver,
+        // the eliminations of the first would be visible to the second.
+        let mut naked = self.clone();
+        naked.deductions.clear();
+        let mut hidden = naked.clone();
+        let _ = naked._find_naked_subsets(Some(house), subset_size, false);
+        let _ = hidden._find_hidden_subsets(Some(house), subset_size, false);
+
+        let n_eliminated_before = self.eliminated_entries.len();
+        let offset = naked.eliminated_entries.len() - n_eliminated_before;
+        for mut deduction in hidden.deductions {
+            if let Deduction::Subsets { conflicts, .. } = &mut deduction {
+                *conflicts = conflicts.start + offset..conflicts.end + offset;
+            }
+            naked.deductions.push(deduction);
+        }
+        naked
+            .eliminated_entries
+            .extend_from_slice(&hidden.eliminated_entries[n_eliminated_before..]);
+        naked.into_deductions()
+    }
+
     #[rustfmt::skip]
     fn into_deductions(self) -> Deductions {
         let Self { deductions, deduced_entries, eliminated_entries, .. } = self;
@@ -227,9 +692,11 @@ impl StrategySolver {
     }
 
     fn update_grid(&mut self) {
-        for &Candidate { cell, digit } in &self.deduced_entries {
-            self.grid.state.0[cell.as_index()] = digit.get();
+        let (ld, _, grid) = self.grid.get_mut();
+        for &Candidate { cell, digit } in &self.deduced_entries[*ld as usize..] {
+            grid.0[cell.as_index()] = digit.get();
         }
+        *ld = self.deduced_entries.len() as _;
     }
 
     /// Try to solve the sudoku using the given `strategies`. Returns a `Result` of the sudoku and a struct containing the series of deductions.
@@ -243,6 +710,163 @@ impl StrategySolver {
         }
     }
 
+    /// Try to solve the sudoku using the given `strategies` like [`solve`](Self::solve), but also return the
+    /// [candidate state](Self::grid_state) of the grid at the point the solver stopped. If the strategies got stuck,
+    /// it can be used to continue manually or with other means. Whether the sudoku was solved can be checked with
+    /// [`Sudoku::is_solved`].
+    pub fn solve_verbose(mut self, strategies: &[Strategy]) -> (Sudoku, Deductions, [CellState; 81]) {
+        self.try_solve(strategies);
+        self.update_grid();
+        let grid_state = self.grid_state();
+        (self.grid.state, self.into_deductions(), grid_state)
+    }
+
+    /// Try to solve the sudoku using the given `strategies` like [`solve`](Self::solve), but without consuming
+    /// the solver. Returns `true`, if the sudoku is solved.
+    ///
+    /// The deductions made so far are available through [`deductions`](Self::deductions) and the grid
+    /// through [`to_sudoku`](Self::to_sudoku). The solver can be used for further solving afterwards.
+    pub fn solve_in_place(&mut self, strategies: &[Strategy]) -> bool {
+        self.try_solve(strategies);
+        self.update_grid_state_caches();
+        self.is_solved()
+    }
+
+    /// Returns a copy of all deductions made so far.
+    pub fn deductions(&self) -> Deductions {
+        self.clone().into_deductions()
+    }
+
+    /// Try to solve the sudoku using the given `strategies` like [`solve`](Self::solve), but also return the state
+    /// of the grid after each deduction, in the order the solver made them. Deductions that only eliminate candidates
+    /// leave the grid unchanged, so there is exactly one grid for each deduction.
+    pub fn solve_with_frames(mut self, strategies: &[Strategy]) -> (Result<Sudoku, Sudoku>, Vec<Sudoku>) {
+        let n_deductions_before = self.deductions.len();
+        let mut frame = self.to_sudoku();
+        self.try_solve(strategies);
+
+        let frames = self.deductions[n_deductions_before..]
+            .iter()
+            .map(|deduction| {
+                if let Some(Candidate { cell, digit }) = deduction.placed_candidate() {
+                    frame.0[cell.as_index()] = digit.get();
+                }
+                frame
+            })
+            .collect();
+
+        let grid = self.to_sudoku();
+        match self.is_solved() {
+            true => (Ok(grid), frames),
+            false => (Err(grid), frames),
+        }
+    }
+
+    /// Try to solve the sudoku step by step using the given `strategies` and record the
+    /// [remaining candidates count](Self::remaining_candidates_count) after each step, e.g. for comparing
+    /// how quickly strategies reduce the candidate space.
+    ///
+    /// In each step, the first of the `strategies` that finds anything is applied once. Unlike
+    /// [`solve`](Self::solve), the first strategy is not applied exhaustively, so there may be more steps
+    /// than needed by `solve`.
+    pub fn solve_with_candidate_trace(
+        mut self,
+        strategies: &[Strategy],
+    ) -> (Result<Sudoku, Sudoku>, Vec<usize>) {
+        let mut trace = vec![];
+        while !self.is_solved() && self.apply_one_step(strategies) {
+            trace.push(self.remaining_candidates_count());
+        }
+
+        let grid = self.to_sudoku();
+        match self.is_solved() {
+            true => (Ok(grid), trace),
+            false => (Err(grid), trace),
+        }
+    }
+
+    /// Applies up to `n_steps` steps and returns the deductions made in them, e.g. to show the next few
+    /// moves as a hint without revealing the whole solution. In each step, the first of the `strategies`
+    /// that finds anything is applied once.
+    ///
+    /// The solver stays advanced by the returned deductions, so solving can continue from there.
+    /// Fewer steps are made, if the sudoku is solved or the strategies are stuck before.
+    pub fn preview(&mut self, strategies: &[Strategy], n_steps: usize) -> Deductions {
+        let n_deductions = self.deductions.len();
+        let n_deduced = self.deduced_entries.len();
+        for _ in 0..n_steps {
+            if self.is_solved() || !self.apply_one_step(strategies) {
+                break;
+            }
+        }
+        self.update_grid_state_caches();
+
+        Deductions {
+            deductions: self.deductions[n_deductions..].to_vec(),
+            deduced_entries: self.deduced_entries[n_deduced..].to_vec(),
+            // the deductions refer to eliminations by their index
+            eliminated_entries: self.eliminated_entries.clone(),
+        }
+    }
+
+    /// Finds the next deduction with the first of the `strategies` that finds anything, applies it and
+    /// returns it, e.g. to show a hint in an interactive game. Calling this again returns the following step.
+    ///
+    /// Unlike [`preview`](Self::preview) with a single step, the solver is advanced by exactly the returned
+    /// deduction, even if the strategy found several at once. The conflicts are owned, so the deduction can be
+    /// kept while solving continues. Returns `None`, if the sudoku is solved or the strategies are stuck.
+    pub fn hint(&mut self, strategies: &[Strategy]) -> Option<Deduction<Vec<Candidate>>> {
+        let deduction = self
+            .clone()
+            .preview(strategies, 1)
+            .get(0)?
+            .map_conflicts(<[Candidate]>::to_vec);
+        self.apply_deduction(deduction.clone())
+            .expect("a deduction found by the solver itself is consistent with its state");
+        Some(deduction)
+    }
+
+    /// Counts how often each of the `strategies` could have been applied during a step by step solve.
+    ///
+    /// Before each step, all deductions that each strategy can find in the current state are tallied,
+    /// then the first strategy that finds anything is applied once, like in
+    /// [`solve_with_candidate_trace`](Self::solve_with_candidate_trace). This continues until the sudoku is
+    /// solved or the strategies are stuck. Every strategy in `strategies` has an entry, even if it was never
+    /// available.
+    ///
+    /// This gives a richer fingerprint of a sudoku than the deductions of [`solve`](Self::solve), but it
+    /// is much slower.
+    pub fn technique_census(mut self, strategies: &[Strategy]) -> BTreeMap<Strategy, usize> {
+        let mut census: BTreeMap<_, _> = strategies.iter().map(|&strategy| (strategy, 0)).collect();
+        while !self.is_solved() {
+            for &strategy in strategies {
+                let mut solver = self.clone();
+                let n_deductions = solver.deductions.len();
+                let _ = strategy.deduce_all(&mut solver, false);
+                *census.entry(strategy).or_insert(0) += solver.deductions.len() - n_deductions;
+            }
+            if !self.apply_one_step(strategies) {
+                break;
+            }
+        }
+        census
+    }
+
+    // Apply the first of the `strategies` that finds anything, once.
+    // Returns `true` if new deductions were made.
+    pub(crate) fn apply_one_step(&mut self, strategies: &[Strategy]) -> bool {
+        let lens = (self.deduced_entries.len(), self.eliminated_entries.len());
+        for strategy in strategies {
+            if strategy.deduce_one(self).is_err() {
+                break;
+            }
+            if lens < (self.deduced_entries.len(), self.eliminated_entries.len()) {
+                return true;
+            }
+        }
+        false
+    }
+
     // FIXME: change name
     /// Try to solve the sudoku using the given `strategies`. Returns `true` if new deductions were made.
     fn try_solve(&mut self, strategies: &[Strategy]) -> bool {
@@ -280,6 +904,48 @@ impl StrategySolver {
         lens < (self.deduced_entries.len(), self.eliminated_entries.len())
     }
 
+    /// Checks whether none of the given `strategies` can make any further progress, although the sudoku
+    /// isn't solved yet. The state of the solver is not changed.
+    ///
+    /// Impossible sudokus are also reported as stuck, unless a strategy still finds something.
+    pub fn is_stuck(&mut self, strategies: &[Strategy]) -> bool {
+        let _ = self.update_cell_poss_house_solved();
+        !self.is_solved() && !self.clone().apply_one_step(strategies)
+    }
+
+    /// Classifies an unsolved sudoku by what it would take to finish it, e.g. after [`solve`](Self::solve)
+    /// returned `Err`. This tells apart a sudoku for which the strategies are too weak from a broken one.
+    /// Returns `None`, if the sudoku is already solved.
+    ///
+    /// The entered digits are checked with the backtracking solver, which takes about as long as
+    /// [`Sudoku::uniqueness`]. If there is a unique solution, it must also agree with the remaining candidates.
+    pub fn remaining_difficulty(&mut self) -> Option<RemainingDifficulty> {
+        let consistent = self.update_cell_poss_house_solved().is_ok();
+        if self.is_solved() {
+            return None;
+        }
+        if !consistent {
+            return Some(RemainingDifficulty::Contradiction);
+        }
+
+        let difficulty = match self.to_sudoku().uniqueness() {
+            Uniqueness::NoSolution => RemainingDifficulty::Contradiction,
+            Uniqueness::Multiple(..) => RemainingDifficulty::NotUnique,
+            Uniqueness::Unique(solution) => {
+                let grid = &self.grid.state;
+                let cell_poss_digits = &self.cell_poss_digits.state;
+                let agrees = Cell::all()
+                    .filter(|cell| grid.0[cell.as_index()] == 0)
+                    .all(|cell| cell_poss_digits[cell].contains(Digit::new(solution.0[cell.as_index()])));
+                match agrees {
+                    true => RemainingDifficulty::NeedsGuessing,
+                    false => RemainingDifficulty::Contradiction,
+                }
+            }
+        };
+        Some(difficulty)
+    }
+
     /// Check whether the sudoku has been completely solved.
     pub fn is_solved(&self) -> bool {
         self.n_solved == 81
@@ -324,9 +990,42 @@ impl StrategySolver {
     }
 
     fn update_house_poss_positions(&mut self) -> Result<(), Unsolvable> {
-        // TODO: this has to do massive amounts of work
-        //       may just be easier to recompute from full grid every time
+        // Every new entry has to be removed from the positions of its 20 neighbors.
+        // With many new entries, it's faster to recompute everything from the cell candidates.
+        let n_new_entries = self.deduced_entries.len() - self.house_poss_positions.next_deduced as usize;
+        if n_new_entries >= HOUSE_POSS_POSITIONS_RECOMPUTE_THRESHOLD
+            && self.recompute_house_poss_positions().is_ok()
+        {
+            return Ok(());
+        }
+        self.update_house_poss_positions_incrementally();
+        Ok(())
+    }
+
+    /// Recompute the possible positions of all digits in all houses from the cell candidates.
+    /// Errors, if the cell candidates couldn't be updated, in which case nothing is changed.
+    fn recompute_house_poss_positions(&mut self) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let (ld, le, house_poss_positions) = self.house_poss_positions.get_mut();
+        *house_poss_positions = HouseArray([DigitArray([Set::NONE; 9]); 27]);
+        for cell in Cell::all() {
+            let row_pos = cell.row_pos().as_set();
+            let col_pos = cell.col_pos().as_set();
+            let block_pos = cell.block_pos().as_set();
+            for digit in cell_poss_digits[cell] {
+                house_poss_positions[cell.row()][digit] |= row_pos;
+                house_poss_positions[cell.col()][digit] |= col_pos;
+                house_poss_positions[cell.block()][digit] |= block_pos;
+            }
+        }
+        *ld = self.deduced_entries.len() as _;
+        *le = self.eliminated_entries.len() as _;
+        Ok(())
+    }
 
+    fn update_house_poss_positions_incrementally(&mut self) {
         let (ld, le, house_poss_positions) = self.house_poss_positions.get_mut();
         // remove now impossible positions from list
         for candidate in &self.eliminated_entries[*le as usize..] {
@@ -377,7 +1076,6 @@ impl StrategySolver {
             house_poss_positions[block][digit] = Set::NONE;
         }
         *ld = self.deduced_entries.len() as _;
-        Ok(())
     }
 
     #[inline(always)]
@@ -662,7 +1360,6 @@ impl StrategySolver {
             let deductions = &mut self.deductions;
 
             naked_singles::find_naked_singles(cell_poss_digits, stop_after_first, |candidate| {
-                deduced_entries.push(candidate);
                 Self::push_new_candidate(
                     grid,
                     deduced_entries,
@@ -738,7 +1435,16 @@ impl StrategySolver {
 
     pub(crate) fn find_naked_subsets(
         &mut self,
-        subset_size: u8,
+        subset_size: usize,
+        stop_after_first: bool,
+    ) -> Result<(), Unsolvable> {
+        self._find_naked_subsets(House::all(), subset_size, stop_after_first)
+    }
+
+    fn _find_naked_subsets(
+        &mut self,
+        houses: impl IntoIterator<Item = House>,
+        subset_size: usize,
         stop_after_first: bool,
     ) -> Result<(), Unsolvable> {
         self.update_cell_poss_house_solved()?;
@@ -750,6 +1456,7 @@ impl StrategySolver {
         naked_subsets::find_naked_subsets(
             cell_poss_digits,
             house_solved_digits,
+            houses,
             subset_size,
             stop_after_first,
             |house, positions, digits| {
@@ -775,7 +1482,16 @@ impl StrategySolver {
 
     pub(crate) fn find_hidden_subsets(
         &mut self,
-        subset_size: u8,
+        subset_size: usize,
+        stop_after_first: bool,
+    ) -> Result<(), Unsolvable> {
+        self._find_hidden_subsets(House::all(), subset_size, stop_after_first)
+    }
+
+    fn _find_hidden_subsets(
+        &mut self,
+        houses: impl IntoIterator<Item = House>,
+        subset_size: usize,
         stop_after_first: bool,
     ) -> Result<(), Unsolvable> {
         self.update_cell_poss_house_solved()?;
@@ -788,6 +1504,7 @@ impl StrategySolver {
         hidden_subsets::find_hidden_subsets(
             house_solved_digits,
             house_poss_positions,
+            houses,
             subset_size,
             stop_after_first,
             |house, digits, positions| {
@@ -825,7 +1542,7 @@ impl StrategySolver {
         self.find_fish(4, stop_after_first)
     }
 
-    fn find_fish(&mut self, target_size: u8, stop_after_first: bool) -> Result<(), Unsolvable> {
+    fn find_fish(&mut self, target_size: usize, stop_after_first: bool) -> Result<(), Unsolvable> {
         self.update_house_poss_positions().unwrap(); // TODO: why is there an unwrap here?
         self.update_cell_poss_house_solved()?;
 
@@ -858,11 +1575,11 @@ impl StrategySolver {
         )
     }
 
-    pub(crate) fn find_mutant_fish(
-        &mut self,
-        target_size: u8,
-        stop_after_first: bool,
-    ) -> Result<(), Unsolvable> {
+    pub(crate) fn find_finned_xwings(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.find_finned_fish(2, stop_after_first)
+    }
+
+    fn find_finned_fish(&mut self, target_size: usize, stop_after_first: bool) -> Result<(), Unsolvable> {
         self.update_house_poss_positions()?;
         self.update_cell_poss_house_solved()?;
 
@@ -871,16 +1588,57 @@ impl StrategySolver {
         let deductions = &mut self.deductions;
         let house_poss_positions = &self.house_poss_positions.state;
 
-        mutant_fish::find_mutant_fish(
+        basic_fish::find_finned_fish(
             house_poss_positions,
             target_size,
             stop_after_first,
-            |digit, candidate_cells, base, cover: Set<House>| {
-                let cover_cells = cover
+            |all_lines, digit, lines, positions_in_line, fins| {
+                // fins are always in a single block
+                let fin_block = fins.into_iter().next().unwrap().block();
+                let conflicts = all_lines
+                    .without(lines)
                     .into_iter()
-                    .map(House::cells)
-                    .fold(Set::NONE, std::ops::BitOr::bitor);
-
+                    .flat_map(|line| positions_in_line.into_iter().map(move |pos| line.cell_at(pos)))
+                    .filter(|&cell| cell.block() == fin_block)
+                    .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                    .map(|cell| Candidate { cell, digit });
+
+                let on_conflict = |conflicts| Deduction::FinnedFish {
+                    lines,
+                    digit,
+                    positions: positions_in_line,
+                    fin_cells: fins,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
+    pub(crate) fn find_mutant_fish(
+        &mut self,
+        target_size: usize,
+        stop_after_first: bool,
+    ) -> Result<(), Unsolvable> {
+        self.update_house_poss_positions()?;
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        mutant_fish::find_mutant_fish(
+            house_poss_positions,
+            target_size,
+            stop_after_first,
+            |digit, candidate_cells, base, cover: Set<House>| {
+                let cover_cells = cover
+                    .into_iter()
+                    .map(House::cells)
+                    .fold(Set::NONE, std::ops::BitOr::bitor);
+
                 let impossible_cells: Set<Cell> = cover_cells ^ candidate_cells;
 
                 let conflicts = impossible_cells
@@ -900,6 +1658,94 @@ impl StrategySolver {
         )
     }
 
+    pub(crate) fn find_skyscraper(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_house_poss_positions()?;
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        skyscraper::find_skyscraper(
+            house_poss_positions,
+            stop_after_first,
+            |digit, base_lines, [end1, end2]| {
+                let conflicts = (end1.peer_set() & end2.peer_set())
+                    .into_iter()
+                    .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                    .map(|cell| Candidate { cell, digit });
+
+                let on_conflict = |conflicts| Deduction::Skyscraper {
+                    base_lines,
+                    digit,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
+    pub(crate) fn find_two_string_kite(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_house_poss_positions()?;
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        two_string_kite::find_two_string_kite(
+            house_poss_positions,
+            stop_after_first,
+            |digit, row, col, [end1, end2]| {
+                let conflicts = (end1.peer_set() & end2.peer_set())
+                    .into_iter()
+                    .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                    .map(|cell| Candidate { cell, digit });
+
+                let on_conflict = |conflicts| Deduction::TwoStringKite {
+                    row,
+                    col,
+                    digit,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
+    pub(crate) fn find_empty_rectangle(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_house_poss_positions()?;
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        empty_rectangle::find_empty_rectangle(
+            house_poss_positions,
+            stop_after_first,
+            |digit, block, conjugate_house, cell| {
+                let conflicts = Some(cell)
+                    .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                    .map(|cell| Candidate { cell, digit });
+
+                let on_conflict = |conflicts| Deduction::EmptyRectangle {
+                    block,
+                    digit,
+                    conjugate_house,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
     pub(crate) fn find_xy_wing(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
         self.update_cell_poss_house_solved()?;
         let cell_poss_digits = &self.cell_poss_digits.state;
@@ -912,7 +1758,7 @@ impl StrategySolver {
             |(cell_hinge, poss_digits_hinge), [(cell_pincer1, poss_digs1), (cell_pincer2, poss_digs2)]| {
                 // TODO: pass common digit as argument to closure
                 let common_digit = (poss_digs1 & poss_digs2).unique().unwrap().unwrap();
-                let common_neighbors = cell_pincer1.neighbors_set() & cell_pincer2.neighbors_set();
+                let common_neighbors = cell_pincer1.peer_set() & cell_pincer2.peer_set();
 
                 let conflicts = common_neighbors
                     .into_iter()
@@ -947,7 +1793,7 @@ impl StrategySolver {
                 // TODO: pass common digit as argument to closure
                 let common_digit = (poss_digs1 & poss_digs2).unique().unwrap().unwrap();
                 let common_neighbors =
-                    cell_hinge.neighbors_set() & cell_pincer1.neighbors_set() & cell_pincer2.neighbors_set();
+                    cell_hinge.peer_set() & cell_pincer1.peer_set() & cell_pincer2.peer_set();
 
                 assert_eq!(common_neighbors.len(), 2);
 
@@ -971,6 +1817,192 @@ impl StrategySolver {
         )
     }
 
+    pub(crate) fn find_w_wing(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_house_poss_positions()?;
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        w_wing::find_w_wing(
+            cell_poss_digits,
+            house_poss_positions,
+            stop_after_first,
+            |[cell1, cell2], connector_house, digit| {
+                let conflicts = (cell1.peer_set() & cell2.peer_set())
+                    .into_iter()
+                    .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                    .map(|cell| Candidate { cell, digit });
+
+                let on_conflict = |conflicts| Deduction::WWing {
+                    cells: cell1.as_set() | cell2,
+                    connector_house,
+                    digit,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
+    pub(crate) fn find_unique_rectangles(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+
+        unique_rectangles::find_unique_rectangles(
+            cell_poss_digits,
+            stop_after_first,
+            |floor, roof, digits, conflict_cells, conflict_digits| {
+                let conflicts = conflict_cells.into_iter().flat_map(|cell| {
+                    (cell_poss_digits[cell] & conflict_digits)
+                        .into_iter()
+                        .map(move |digit| Candidate { cell, digit })
+                });
+
+                let on_conflict = |conflicts| Deduction::UniqueRectangle {
+                    floor: floor[0].as_set() | floor[1],
+                    roof: roof[0].as_set() | roof[1],
+                    digits,
+                    conflicts,
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
+    pub(crate) fn find_bug(&mut self) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+
+        bug::find_bug(cell_poss_digits, |cell, digit| {
+            let conflicts = cell_poss_digits[cell]
+                .without(digit.as_set())
+                .into_iter()
+                .map(|digit| Candidate { cell, digit });
+
+            let on_conflict = |conflicts| Deduction::Bug {
+                cell,
+                digit,
+                conflicts,
+            };
+
+            Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict);
+        })
+    }
+
+    pub(crate) fn find_remote_pairs(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+
+        remote_pairs::find_remote_pairs(cell_poss_digits, stop_after_first, |digits, colors| {
+            let cells = colors[0] | colors[1];
+            let sees_color = |color: Set<Cell>| {
+                color
+                    .into_iter()
+                    .fold(Set::NONE, |peers, cell| peers | cell.peer_set())
+            };
+            let conflicts = (sees_color(colors[0]) & sees_color(colors[1]))
+                .without(cells)
+                .into_iter()
+                .flat_map(|cell| {
+                    (cell_poss_digits[cell] & digits)
+                        .into_iter()
+                        .map(move |digit| Candidate { cell, digit })
+                });
+
+            let on_conflict = |conflicts| Deduction::RemotePairs {
+                cells,
+                digits,
+                conflicts,
+            };
+
+            Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+        })
+    }
+
+    pub(crate) fn find_xy_chain(
+        &mut self,
+        max_length: usize,
+        stop_after_first: bool,
+    ) -> Result<(), Unsolvable> {
+        self.update_cell_poss_house_solved()?;
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+
+        xy_chain::find_xy_chain(cell_poss_digits, max_length, stop_after_first, |chain, digit| {
+            let (first, last) = (chain[0], chain[chain.len() - 1]);
+            let conflicts = (first.peer_set() & last.peer_set())
+                .into_iter()
+                .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                .map(|cell| Candidate { cell, digit });
+
+            let on_conflict = |conflicts| Deduction::XyChain {
+                cells: chain.iter().fold(Set::NONE, |cells, &cell| cells | cell),
+                ends: first.as_set() | last,
+                digit,
+                conflicts,
+            };
+
+            Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+        })
+    }
+
+    pub(crate) fn find_x_chain(
+        &mut self,
+        grouped: bool,
+        max_length: usize,
+        stop_after_first: bool,
+    ) -> Result<(), Unsolvable> {
+        self.update_house_poss_positions()?;
+        self.update_cell_poss_house_solved()?;
+
+        let cell_poss_digits = &self.cell_poss_digits.state;
+        let eliminated_entries = &mut self.eliminated_entries;
+        let deductions = &mut self.deductions;
+        let house_poss_positions = &self.house_poss_positions.state;
+
+        x_chain::find_x_chain(
+            house_poss_positions,
+            grouped,
+            max_length,
+            stop_after_first,
+            |digit, chain| {
+                let conflicts = x_chain::chain_conflict_cells(chain)
+                    .into_iter()
+                    .filter(|&cell| cell_poss_digits[cell].contains(digit))
+                    .map(|cell| Candidate { cell, digit });
+
+                let cells = chain.iter().fold(Set::NONE, |cells, &node| cells | node);
+                let on_conflict = |conflicts| match grouped {
+                    true => Deduction::GroupedChain {
+                        digit,
+                        cells,
+                        ends: [chain[0], chain[chain.len() - 1]],
+                        conflicts,
+                    },
+                    false => Deduction::XChain {
+                        digit,
+                        cells,
+                        conflicts,
+                    },
+                };
+
+                Self::enter_conflicts(eliminated_entries, deductions, conflicts, on_conflict)
+            },
+        )
+    }
+
     /*
     pub(crate) fn find_singles_chain(&mut self, stop_after_first: bool) -> Result<(), Unsolvable> {
         #[derive(Copy, Clone, PartialEq, Eq)]
@@ -1223,7 +2255,8 @@ mod test {
 
     #[test]
     fn strategy_solver_correct_solution_medium_sudokus() {
-        // the 9th sudoku requires more advanced strategies
+        // the 9th sudoku can't be solved by any combination of the implemented strategies,
+        // it needs techniques like forcing chains
         let filter_9 = |vec: Vec<_>| {
             vec.into_iter()
                 .enumerate()
@@ -1240,6 +2273,904 @@ mod test {
         strategy_solver_correct_solution(sudokus, solved_sudokus, StrategySolver::solve);
     }
 
+    #[test]
+    fn strategy_solver_correct_solution_finned_xwing_sudokus() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/finned_xwing_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!(
+            "../../sudokus/Lines/solved_finned_xwing_sudokus.txt"
+        ));
+
+        // none of these can be solved without the finned x-wing
+        // or the skyscraper, two-string kite and x-chain, which find many of the same eliminations
+        let strategies_without_finned_xwing = Strategy::ALL
+            .iter()
+            .filter(|strategy| {
+                ![
+                    Strategy::FinnedXWing,
+                    Strategy::Skyscraper,
+                    Strategy::TwoStringKite,
+                    Strategy::XChain,
+                ]
+                .contains(strategy)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        for &sudoku in &sudokus {
+            assert!(StrategySolver::from_sudoku(sudoku)
+                .solve(&strategies_without_finned_xwing)
+                .is_err());
+        }
+
+        strategy_solver_correct_solution(sudokus, solved_sudokus, StrategySolver::solve);
+    }
+
+    #[test]
+    fn single_hints() {
+        use crate::strategy::{SingleHint, SingleKind};
+
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        let solution = sudoku.solution().unwrap();
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        let hints = solver.single_hints().unwrap();
+        assert!(!hints.is_empty());
+
+        let mut candidates = vec![];
+        for &SingleHint {
+            cell,
+            digit,
+            kind,
+            house,
+        } in &hints
+        {
+            assert_eq!(solution.to_bytes()[cell.as_index()], digit.get());
+            match (kind, house) {
+                (SingleKind::Naked, None) => assert_eq!(solver.cell_poss_digits.state[cell], digit.as_set()),
+                (SingleKind::Hidden, Some(house_type)) => {
+                    let house = match house_type {
+                        HouseType::Row(row) => row.house(),
+                        HouseType::Col(col) => col.house(),
+                        HouseType::Block(block) => block.house(),
+                    };
+                    assert!(house.cells().contains(cell));
+                    let positions = house
+                        .cells()
+                        .into_iter()
+                        .filter(|&other| solver.cell_poss_digits.state[other].contains(digit))
+                        .count();
+                    assert_eq!(positions, 1);
+                }
+                _ => panic!("inconsistent hint {:?}", (kind, house)),
+            }
+            candidates.push(Candidate { cell, digit });
+        }
+        candidates.sort();
+        candidates.dedup();
+        assert_eq!(candidates, solver.obvious_singles().unwrap());
+    }
+
+    #[test]
+    fn obvious_singles_are_correct_and_not_applied() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+
+        for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus) {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            let singles = solver.obvious_singles().unwrap();
+            assert!(!singles.is_empty());
+            for candidate in singles {
+                assert_eq!(sudoku.0[candidate.cell.as_index()], 0);
+                assert_eq!(solved_sudoku.0[candidate.cell.as_index()], candidate.digit.get());
+            }
+            assert!(solver.deductions.is_empty());
+            assert_eq!(solver.to_sudoku(), sudoku);
+        }
+
+        // r1c9 has no candidates left
+        let impossible = Sudoku::from_str_line(
+            "12345678.........9...............................................................",
+        )
+        .unwrap();
+        assert_eq!(
+            StrategySolver::from_sudoku(impossible).obvious_singles(),
+            Err(Unsolvable)
+        );
+    }
+
+    #[test]
+    fn subsets_in_house() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_medium_sudokus.txt"));
+        let mut n_found = 0;
+
+        for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus) {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            let _ = solver.try_solve(&[Strategy::NakedSingles, Strategy::HiddenSingles]);
+            let n_deductions = solver.deductions.len();
+            let n_eliminated = solver.eliminated_entries.len();
+
+            for house in House::all() {
+                for subset_size in 2..=4 {
+                    let deductions = solver.subsets_in_house(house, subset_size);
+                    for deduction in deductions.iter() {
+                        let conflicts = match deduction {
+                            Deduction::Subsets {
+                                house: found_house,
+                                conflicts,
+                                ..
+                            } => {
+                                assert_eq!(found_house, house);
+                                conflicts
+                            }
+                            _ => panic!("unexpected deduction {:?}", deduction),
+                        };
+                        assert!(!conflicts.is_empty());
+                        for candidate in conflicts {
+                            assert_ne!(solved_sudoku.0[candidate.cell.as_index()], candidate.digit.get());
+                        }
+                        n_found += 1;
+                    }
+                }
+            }
+            assert_eq!(solver.deductions.len(), n_deductions);
+            assert_eq!(solver.eliminated_entries.len(), n_eliminated);
+        }
+        assert!(n_found > 0);
+    }
+
+    #[test]
+    fn deductions_compare_by_value() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[2];
+        let strategies = [
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            Strategy::NakedPairs,
+        ];
+        let (_, deductions) = StrategySolver::from_sudoku(sudoku).solve(&strategies).unwrap();
+
+        let conflicts = [Candidate::new(49, 3), Candidate::new(31, 3)];
+        let expected = Deduction::LockedCandidates {
+            digit: Digit::new(3),
+            miniline: MiniLine::new(13),
+            is_pointing: false,
+            conflicts: &conflicts[..],
+        };
+        assert_eq!(deductions.get(28), Some(expected));
+        assert!(deductions.iter().zip(deductions.iter()).all(|(a, b)| a == b));
+
+        let unique = deductions.iter().collect::<std::collections::HashSet<_>>();
+        assert_eq!(unique.len(), deductions.len());
+    }
+
+    #[test]
+    fn insert_candidate_and_propagate() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+        for (sudoku, solution) in sudokus.into_iter().zip(solved_sudokus).take(20) {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            let cell = Cell::all().find(|cell| sudoku.0[cell.as_index()] == 0).unwrap();
+            let candidate = Candidate::new(cell.get(), solution.0[cell.as_index()]);
+
+            let deductions = solver.insert_candidate_and_propagate(candidate).unwrap();
+            assert!(deductions.iter().all(|deduction| {
+                let strategy = deduction.strategy();
+                strategy == Strategy::NakedSingles || strategy == Strategy::HiddenSingles
+            }));
+            let partial = solver.to_sudoku();
+            for cell in Cell::all() {
+                let digit = partial.0[cell.as_index()];
+                assert!(digit == 0 || digit == solution.0[cell.as_index()]);
+            }
+            assert!(
+                solver.is_stuck(&[Strategy::NakedSingles, Strategy::HiddenSingles]) || solver.is_solved()
+            );
+
+            // the cell is now filled
+            assert!(solver
+                .insert_candidate_and_propagate(candidate)
+                .unwrap()
+                .is_empty());
+            let other_digit = candidate.digit.get() % 9 + 1;
+            let conflict = Candidate::new(cell.get(), other_digit);
+            assert!(solver.insert_candidate_and_propagate(conflict).is_none());
+        }
+    }
+
+    #[test]
+    fn solve_in_place() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"));
+        let singles = [Strategy::NakedSingles, Strategy::HiddenSingles];
+        for &sudoku in sudokus.iter().take(10) {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            let solved = solver.solve_in_place(Strategy::ALL);
+            let (result, deductions) = match StrategySolver::from_sudoku(sudoku).solve(Strategy::ALL) {
+                Ok((grid, deductions)) => (Ok(grid), deductions),
+                Err((grid, deductions)) => (Err(grid), deductions),
+            };
+            assert_eq!(result.is_ok(), solved);
+            assert_eq!(result.unwrap_or_else(|grid| grid), solver.to_sudoku());
+            assert_eq!(solver.deductions(), deductions);
+
+            // solving can continue after a partial solve
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            solver.solve_in_place(&singles);
+            let n_singles = solver.deductions().len();
+            assert_eq!(solver.solve_in_place(Strategy::ALL), solved);
+            assert!(solver.deductions().len() >= n_singles);
+        }
+    }
+
+    #[test]
+    fn forbid() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        let solution = sudoku.solution().unwrap();
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        let grid_state = solver.grid_state();
+
+        // a clue can't be forbidden and no cell may be left empty
+        let clue = Cell::all().find(|cell| sudoku.0[cell.as_index()] != 0).unwrap();
+        let clue_digit = Digit::new(sudoku.0[clue.as_index()]);
+        assert_eq!(solver.forbid(clue.as_set(), clue_digit.as_set()), Err(Unsolvable));
+        let empty_cells = Cell::all().filter(|cell| sudoku.0[cell.as_index()] == 0);
+        assert_eq!(
+            solver.forbid(empty_cells.fold(Set::NONE, |cells, cell| cells | cell), Set::ALL),
+            Err(Unsolvable)
+        );
+        assert_eq!(solver.grid_state()[..], grid_state[..]);
+
+        // forbid all but the correct digit
+        let cell = Cell::all().find(|cell| sudoku.0[cell.as_index()] == 0).unwrap();
+        let digit = Digit::new(solution.0[cell.as_index()]);
+        solver
+            .forbid(cell.as_set(), Set::ALL.without(digit.as_set()))
+            .unwrap();
+        assert_eq!(solver.cell_state(cell), CellState::Candidates(digit.as_set()));
+
+        let singles = [Strategy::NakedSingles, Strategy::HiddenSingles];
+        assert_eq!(solver.solve(&singles).ok().map(|(grid, _)| grid), Some(solution));
+    }
+
+    #[test]
+    fn cell_exclusions() {
+        // needs more than the basic strategies
+        let sudoku = Sudoku::from_str_line(
+            "9.54...1...6.....5.8.597...8.....6.9...2.5...5.7.....3...154.9.4.....3...1...35.2",
+        )
+        .unwrap();
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        assert!(!solver.solve_in_place(Strategy::ALL));
+        let grid = solver.to_sudoku();
+
+        let mut n_deduced = 0;
+        for cell in Cell::all() {
+            let exclusions = solver.cell_exclusions(cell);
+            if grid.0[cell.as_index()] != 0 {
+                assert!(exclusions.is_empty());
+                continue;
+            }
+            let excluded = exclusions
+                .iter()
+                .fold(Set::NONE, |digits, &(digit, _)| digits | digit);
+            assert_eq!(excluded, !solver.cell_poss_digits.state[cell]);
+
+            for (digit, reason) in exclusions {
+                match reason {
+                    ExclusionReason::Peer(peer) => {
+                        assert!(cell.peer_set().contains(peer));
+                        assert_eq!(grid.0[peer.as_index()], digit.get());
+                    }
+                    ExclusionReason::Deduction(index) => {
+                        n_deduced += 1;
+                        let conflicts = solver.deductions[index].conflicts_range();
+                        assert!(solver.eliminated_entries[conflicts].contains(&Candidate { cell, digit }));
+                    }
+                    ExclusionReason::External => panic!("no candidates were eliminated externally"),
+                }
+            }
+        }
+        assert!(n_deduced > 0);
+
+        let cell = Cell::all().find(|cell| grid.0[cell.as_index()] == 0).unwrap();
+        let digit = solver.cell_poss_digits.state[cell].into_iter().next().unwrap();
+        solver.forbid(cell.as_set(), digit.as_set()).unwrap();
+        assert!(solver
+            .cell_exclusions(cell)
+            .contains(&(digit, ExclusionReason::External)));
+    }
+
+    #[test]
+    fn grid_state_caches() {
+        let sudoku = Sudoku::from_str_line(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        let solution = sudoku.solution().unwrap();
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        assert!(!solver.grid_state_caches_up_to_date());
+
+        // the cached state must match the one computed from scratch
+        let check = |solver: &StrategySolver| {
+            assert!(solver.grid_state_caches_up_to_date());
+            let mut updated = solver.clone();
+            updated.update_grid_state_caches();
+            assert_eq!(solver.grid_state()[..], updated.grid_state_from_caches()[..]);
+        };
+
+        let cell = Cell::new(0);
+        let digit = Digit::new(solution.0[0]);
+        let wrong_digit = Digit::new(solution.0[1] % 9 + 1);
+        solver
+            .forbid(Cell::new(1).as_set(), wrong_digit.as_set())
+            .unwrap();
+        check(&solver);
+        solver.insert_candidate(Candidate { cell, digit }).unwrap();
+        check(&solver);
+        solver.insert_candidate_and_propagate(Candidate::new(2, solution.0[2]));
+        check(&solver);
+        solver.preview(&[Strategy::HiddenSingles], 3);
+        check(&solver);
+        assert!(solver.solve_in_place(&[Strategy::NakedSingles, Strategy::HiddenSingles]));
+        check(&solver);
+    }
+
+    #[test]
+    fn solve_verbose() {
+        let sudoku = Sudoku::from_str_line(
+            "9.54...1...6.....5.8.597...8.....6.9...2.5...5.7.....3...154.9.4.....3...1...35.2",
+        )
+        .unwrap();
+        let strategies = &[
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+        ];
+
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        assert!(!solver.solve_in_place(strategies));
+        let (grid, deductions, grid_state) = StrategySolver::from_sudoku(sudoku).solve_verbose(strategies);
+        assert!(!grid.is_solved());
+        assert_eq!(grid, solver.to_sudoku());
+        assert_eq!(deductions.len(), solver.deductions().len());
+        assert_eq!(grid_state[..], solver.grid_state()[..]);
+
+        // the candidates left over still contain the solution
+        let solution = sudoku.solution().unwrap();
+        for (state, &digit) in grid_state.iter().zip(solution.0.iter()) {
+            let digit = Digit::new(digit);
+            match *state {
+                CellState::Digit(d) => assert_eq!(d, digit),
+                CellState::Candidates(cands) => assert!(cands.contains(digit)),
+            }
+        }
+    }
+
+    #[test]
+    fn remaining_difficulty() {
+        // needs more than the basic strategies
+        let sudoku = Sudoku::from_str_line(
+            "9.54...1...6.....5.8.597...8.....6.9...2.5...5.7.....3...154.9.4.....3...1...35.2",
+        )
+        .unwrap();
+        let solution = sudoku.solution().unwrap();
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        assert!(!solver.solve_in_place(Strategy::ALL));
+        assert_eq!(
+            solver.remaining_difficulty(),
+            Some(RemainingDifficulty::NeedsGuessing)
+        );
+
+        // eliminating the correct digit of a cell contradicts the unique solution
+        let mut broken = solver.clone();
+        let cell = Cell::all()
+            .find(|&cell| solver.cell_poss_digits.state[cell].len() > 2)
+            .unwrap();
+        let digit = Digit::new(solution.0[cell.as_index()]);
+        broken.forbid(cell.as_set(), digit.as_set()).unwrap();
+        assert_eq!(
+            broken.remaining_difficulty(),
+            Some(RemainingDifficulty::Contradiction)
+        );
+
+        let mut solver = StrategySolver::from_sudoku(solution);
+        assert_eq!(solver.remaining_difficulty(), None);
+
+        let mut solver = StrategySolver::from_sudoku(Sudoku::empty());
+        assert_eq!(
+            solver.remaining_difficulty(),
+            Some(RemainingDifficulty::NotUnique)
+        );
+
+        // no digit is left for the first cell
+        let mut bytes = [0; 81];
+        bytes[1..9].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        bytes[9] = 9;
+        let mut solver = StrategySolver::from_sudoku(Sudoku::from_bytes(bytes).unwrap());
+        assert_eq!(
+            solver.remaining_difficulty(),
+            Some(RemainingDifficulty::Contradiction)
+        );
+    }
+
+    #[test]
+    fn recomputed_house_poss_positions_match_updated() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/hard_sudokus.txt"));
+        for sudoku in sudokus {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            loop {
+                let mut updated = solver.clone();
+                updated.update_house_poss_positions_incrementally();
+                let mut recomputed = solver.clone();
+                recomputed.recompute_house_poss_positions().unwrap();
+                assert_eq!(
+                    updated.house_poss_positions.state,
+                    recomputed.house_poss_positions.state
+                );
+                if !solver.apply_one_step(Strategy::ALL) {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn canonicalize_deductions() {
+        // applied exhaustively, naked pairs are found over and over with the same eliminations
+        let strategies = [
+            Strategy::NakedPairs,
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+        ];
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"));
+        let mut n_dropped = 0;
+        for sudoku in sudokus {
+            let deductions = match StrategySolver::from_sudoku(sudoku).solve(&strategies) {
+                Ok((_, deductions)) | Err((_, deductions)) => deductions,
+            };
+            let canonical = deductions.canonicalize();
+            n_dropped += deductions.len() - canonical.len();
+            assert_eq!(canonical.canonicalize(), canonical);
+
+            let placed = |deductions: &Deductions| {
+                deductions
+                    .iter()
+                    .filter_map(|deduction| deduction.placed_candidate())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(placed(&canonical), placed(&deductions));
+
+            // the remaining deductions are a subsequence of the original ones
+            let mut original = deductions.iter();
+            for deduction in canonical.iter() {
+                assert!(original.any(|other| other == deduction));
+            }
+        }
+        assert!(n_dropped > 0);
+    }
+
+    #[test]
+    fn is_stuck() {
+        let singles = &[Strategy::NakedSingles, Strategy::HiddenSingles];
+        let mut n_stuck = 0;
+        for sudoku in read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt")) {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            assert!(solver.is_stuck(&[]));
+            assert!(!solver.is_stuck(Strategy::ALL));
+
+            while !solver.is_stuck(singles) {
+                assert!(solver.apply_one_step(singles));
+                let _ = solver.update_cell_poss_house_solved();
+                if solver.is_solved() {
+                    assert!(!solver.is_stuck(singles));
+                    break;
+                }
+            }
+            if !solver.is_solved() {
+                assert!(!solver.clone().apply_one_step(singles));
+                n_stuck += 1;
+            }
+        }
+        assert!(n_stuck > 0);
+    }
+
+    #[test]
+    fn solve_with_candidate_trace() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+        for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus).take(10) {
+            let solver = StrategySolver::from_sudoku(sudoku);
+            let n_candidates = solver.clone().remaining_candidates_count();
+            let (result, trace) = solver.solve_with_candidate_trace(Strategy::ALL);
+
+            assert_eq!(result, Ok(solved_sudoku));
+            assert_eq!(trace.last(), Some(&0));
+            let mut previous = n_candidates;
+            for &count in &trace {
+                assert!(count < previous);
+                previous = count;
+            }
+        }
+    }
+
+    #[test]
+    fn determined_houses() {
+        let solved_sudoku = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"))[0];
+        let mut bytes = solved_sudoku.to_bytes();
+        for byte in &mut bytes[..9] {
+            *byte = 0;
+        }
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+
+        // the first row, all columns and the first 3 blocks are missing one or more digits
+        // that are all naked singles
+        let expected: Vec<House> = std::iter::once(Row::new(0).house())
+            .chain((0..9).map(|col| Col::new(col).house()))
+            .chain((0..3).map(|block| Block::new(block).house()))
+            .collect();
+        let mut determined = solver.determined_houses().unwrap();
+        determined.sort_by_key(|house| house.get());
+        assert_eq!(determined, expected);
+
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        assert!(solver.solve_in_place(&[Strategy::NakedSingles]));
+        assert!(solver.determined_houses().unwrap().is_empty());
+
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        assert!(StrategySolver::from_sudoku(sudoku)
+            .determined_houses()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn preview() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"))[0];
+        let solved_sudoku = read_sudokus(include_str!("../../sudokus/Lines/solved_medium_sudokus.txt"))[0];
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+
+        let first = solver.preview(Strategy::ALL, 3);
+        assert_eq!(first.len(), 3);
+        let second = solver.preview(Strategy::ALL, 2);
+        assert_eq!(second.len(), 2);
+
+        let all = solver.deductions();
+        let previews: Vec<_> = first.iter().chain(second.iter()).collect();
+        assert_eq!(all.iter().collect::<Vec<_>>(), previews);
+
+        let rest = solver.preview(Strategy::ALL, usize::MAX);
+        assert!(!rest.is_empty());
+        assert!(solver.is_solved());
+        assert_eq!(solver.to_sudoku(), solved_sudoku);
+        assert!(solver.preview(Strategy::ALL, 1).is_empty());
+    }
+
+    #[test]
+    fn hint() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"))[0];
+        let solved_sudoku = read_sudokus(include_str!("../../sudokus/Lines/solved_medium_sudokus.txt"))[0];
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+
+        let first = solver.clone().preview(Strategy::ALL, 1);
+        let hint = solver.hint(Strategy::ALL).unwrap();
+        assert_eq!(
+            Some(hint.clone()),
+            first
+                .get(0)
+                .map(|deduction| deduction.map_conflicts(<[Candidate]>::to_vec))
+        );
+        assert_eq!(solver.deductions().len(), 1);
+
+        // every hint advances the solver by one deduction
+        let mut hints = vec![hint];
+        while let Some(hint) = solver.hint(Strategy::ALL) {
+            hints.push(hint);
+            assert_eq!(solver.deductions().len(), hints.len());
+        }
+        assert!(solver.is_solved());
+        assert_eq!(solver.to_sudoku(), solved_sudoku);
+        assert!(solver.hint(Strategy::ALL).is_none());
+    }
+
+    #[test]
+    fn apply_deduction() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"))[0];
+        let (solution, deductions) = StrategySolver::from_sudoku(sudoku).solve(Strategy::ALL).unwrap();
+
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        for deduction in deductions.iter() {
+            assert!(solver
+                .apply_deduction(deduction.map_conflicts(<[Candidate]>::to_vec))
+                .is_ok());
+        }
+        assert!(solver.is_solved());
+        assert_eq!(solver.to_sudoku(), solution);
+        assert!(solver.deductions().iter().eq(deductions.iter()));
+
+        // neither entering a digit twice nor eliminating from solved cells is allowed
+        let first = deductions
+            .iter()
+            .find_map(|deduction| deduction.placed_candidate())
+            .unwrap();
+        assert!(solver.apply_deduction(Deduction::NakedSingles(first)).is_err());
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        let clue = sudoku.clues().next().unwrap();
+        let elimination = Deduction::LockedCandidates {
+            digit: clue.digit,
+            miniline: MiniLine::new(0),
+            is_pointing: true,
+            conflicts: vec![clue],
+        };
+        assert!(solver.apply_deduction(elimination).is_err());
+        assert!(solver.deductions().is_empty());
+    }
+
+    #[test]
+    fn technique_census() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"))[0];
+        let census = StrategySolver::from_sudoku(sudoku).technique_census(Strategy::ALL);
+        let mut strategies = Strategy::ALL.to_vec();
+        strategies.sort();
+        assert_eq!(census.keys().cloned().collect::<Vec<_>>(), strategies);
+
+        // every strategy applied in a step has been available at least once
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        while solver.apply_one_step(Strategy::ALL) {}
+        assert!(solver.is_solved());
+        for deduction in solver.deductions().iter() {
+            assert!(census[&deduction.strategy()] > 0);
+        }
+        assert!(
+            census[&Strategy::NakedSingles] + census[&Strategy::HiddenSingles]
+                >= 81 - sudoku.n_clues() as usize
+        );
+    }
+
+    #[test]
+    fn remaining_candidates_count() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        let solved_sudoku = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"))[0];
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        let grid_state = solver.grid_state();
+        let expected: usize = grid_state
+            .iter()
+            .map(|&state| match state {
+                CellState::Candidates(digits) => digits.len(),
+                CellState::Digit(_) => 0,
+            })
+            .sum();
+        let mut count = solver.remaining_candidates_count();
+        assert_eq!(count, expected);
+
+        for (cell, digit) in Cell::all().zip(solved_sudoku.iter()) {
+            if sudoku.0[cell.as_index()] != 0 {
+                continue;
+            }
+            let digit = Digit::new(digit.unwrap());
+            solver.insert_candidate(Candidate { cell, digit }).unwrap();
+            let new_count = solver.remaining_candidates_count();
+            assert!(new_count < count);
+            count = new_count;
+        }
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn score_and_strategy_counts() {
+        let easy = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        let hard = read_sudokus(include_str!("../../sudokus/Lines/finned_xwing_sudokus.txt"))[0];
+        let (_, easy_deductions) = StrategySolver::from_sudoku(easy).solve(Strategy::ALL).unwrap();
+        let (_, hard_deductions) = StrategySolver::from_sudoku(hard).solve(Strategy::ALL).unwrap();
+        assert!(easy_deductions.score() < hard_deductions.score());
+
+        let counts = hard_deductions.strategy_counts();
+        assert_eq!(counts.values().sum::<usize>(), hard_deductions.len());
+        assert!(counts.values().all(|&count| count > 0));
+        let score: usize = counts
+            .iter()
+            .map(|(strategy, &count)| strategy.score_weight() as usize * count)
+            .sum();
+        assert_eq!(score, hard_deductions.score() as usize);
+    }
+
+    #[test]
+    fn conjugate_pairs() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/medium_sudokus.txt"))[0];
+        let mut solver = StrategySolver::from_sudoku(sudoku);
+        for digit in Digit::all() {
+            let pairs = solver.conjugate_pairs(digit).unwrap();
+            let grid_state = solver.grid_state();
+            let has_candidate = |cell: Cell| match grid_state[cell.as_index()] {
+                CellState::Candidates(digits) => digits.contains(digit),
+                CellState::Digit(_) => false,
+            };
+            for &(house, cell1, cell2) in &pairs {
+                assert!(cell1 < cell2);
+                let cells = house
+                    .cells()
+                    .into_iter()
+                    .filter(|&cell| has_candidate(cell))
+                    .collect::<Vec<_>>();
+                assert_eq!(cells, [cell1, cell2]);
+            }
+            let n_houses = House::all()
+                .filter(|house| {
+                    house
+                        .cells()
+                        .into_iter()
+                        .filter(|&cell| has_candidate(cell))
+                        .count()
+                        == 2
+                })
+                .count();
+            assert_eq!(pairs.len(), n_houses);
+        }
+    }
+
+    #[test]
+    fn link_graph_edges_are_conjugate_pairs() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+
+        for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus) {
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            let grid_state = solver.grid_state();
+            for digit in Digit::all() {
+                let graph = solver.link_graph(digit).unwrap();
+                assert_eq!(graph.digit, digit);
+                for cell in Cell::all() {
+                    let is_candidate = match grid_state[cell.as_index()] {
+                        CellState::Candidates(digits) => digits.contains(digit),
+                        CellState::Digit(_) => false,
+                    };
+                    assert_eq!(graph.nodes.contains(cell), is_candidate);
+                }
+                for link in &graph.edges {
+                    let [first, second] = link.cells;
+                    assert!(first < second);
+                    assert!(link.house.cells().contains(first) && link.house.cells().contains(second));
+                    // exactly one of the two cells holds the digit in the solution
+                    let n_solved = link
+                        .cells
+                        .iter()
+                        .filter(|cell| solved_sudoku.0[cell.as_index()] == digit.get())
+                        .count();
+                    assert_eq!(n_solved, 1);
+                }
+                let dot = graph.to_dot();
+                assert_eq!(dot.lines().count(), 2 + graph.nodes.len() + graph.edges.len());
+            }
+        }
+
+        // r1c9 has no candidates left
+        let impossible = Sudoku::from_str_line(
+            "12345678.........9...............................................................",
+        )
+        .unwrap();
+        let mut solver = StrategySolver::from_sudoku(impossible);
+        assert_eq!(solver.link_graph(Digit::new(1)), Err(Unsolvable));
+    }
+
+    #[test]
+    fn solve_with_frames() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+
+        for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus) {
+            let (_, deductions) = StrategySolver::from_sudoku(sudoku).solve(Strategy::ALL).unwrap();
+            let (result, frames) = StrategySolver::from_sudoku(sudoku).solve_with_frames(Strategy::ALL);
+            assert_eq!(result, Ok(solved_sudoku));
+            assert_eq!(frames.len(), deductions.len());
+            assert_eq!(frames.last(), Some(&solved_sudoku));
+
+            let mut previous = sudoku;
+            for (frame, deduction) in frames.into_iter().zip(deductions.iter()) {
+                let n_entered = frame.n_clues() - previous.n_clues();
+                match deduction.placed_candidate() {
+                    Some(_) => assert_eq!(n_entered, 1),
+                    None => assert_eq!(frame, previous),
+                }
+                previous = frame;
+            }
+        }
+    }
+
+    #[test]
+    fn explicit_singles() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        let solved_sudokus = read_sudokus(include_str!("../../sudokus/Lines/solved_easy_sudokus.txt"));
+
+        for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus) {
+            let solver = StrategySolver::from_sudoku(sudoku).explicit_singles(true);
+            let (solution, deductions) = solver.solve(Strategy::ALL).unwrap();
+            assert_eq!(solution, solved_sudoku);
+
+            // every empty cell is filled by exactly one deduction
+            let mut filled = Set::NONE;
+            for candidate in deductions
+                .iter()
+                .filter_map(|deduction| deduction.placed_candidate())
+            {
+                assert!(!filled.contains(candidate.cell));
+                assert_eq!(sudoku.to_bytes()[candidate.cell.as_index()], 0);
+                filled |= candidate.cell;
+            }
+            assert_eq!(filled.len() + sudoku.n_clues() as usize, 81);
+        }
+    }
+
+    #[test]
+    fn naked_singles_deduced_once() {
+        let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
+        for sudoku in sudokus {
+            for &explicit_singles in &[false, true] {
+                let mut solver = StrategySolver::from_sudoku(sudoku).explicit_singles(explicit_singles);
+                solver.solve_in_place(&[Strategy::NakedSingles, Strategy::HiddenSingles]);
+
+                // every clue and every entry is recorded exactly once
+                let mut filled = Set::NONE;
+                for candidate in &solver.deduced_entries {
+                    assert!(!filled.contains(candidate.cell));
+                    filled |= candidate.cell;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn display_candidates() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        let grid = StrategySolver::from_sudoku(sudoku).display_candidates();
+
+        #[rustfmt::skip]
+        let expected_start =
+"┌─────────────┬─────────────┬─────────────┐
+│ ... ...     │ ...     1.. │     ... ... │
+│ 45. 45.  3  │ 4..  2  4.. │  6  .5. .5. │
+│ ... 78.     │ ..9     7.. │     789 7.. │
+│             │             │             │
+│     .2. ... │     ...     │ ... .2.     │
+│  9  4.6 4.. │  3  4..  5  │ ... ...  1  │
+│     78. 7.. │     7..     │ 78. 78.     │
+│             │             │             │
+";
+        assert!(grid.starts_with(expected_start), "{}", grid);
+        assert_eq!(grid.lines().count(), 37);
+        assert!(grid.ends_with("└─────────────┴─────────────┴─────────────┘\n"));
+    }
+
+    #[test]
+    fn candidate_grid_string() {
+        let sudoku = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"))[0];
+        let solver = StrategySolver::from_sudoku(sudoku);
+        let grid = solver.candidate_grid_string();
+
+        #[rustfmt::skip]
+        let expected_start =
+"+-------------+-------------+-------------+
+| ... ...     | ...     1.. |     ... ... |
+| 45. 45.  3  | 4..  2  4.. |  6  .5. .5. |
+| ... 78.     | ..9     7.. |     789 7.. |
+|             |             |             |
+";
+        assert!(grid.starts_with(expected_start), "{}", grid);
+        assert_eq!(grid.lines().count(), 37);
+        assert!(grid.lines().all(|line| line.len() == 43));
+
+        // same layout as the unicode grid
+        let unicode_grid = solver.display_candidates();
+        let translated = unicode_grid
+            .chars()
+            .map(|ch| match ch {
+                '─' => '-',
+                '│' => '|',
+                ' ' | '.' | '\n' | '1'..='9' => ch,
+                _ => '+',
+            })
+            .collect::<String>();
+        assert_eq!(grid, translated);
+    }
+
     #[test]
     fn roundtrip_grid_state_str() {
         let sudokus = read_sudokus(include_str!("../../sudokus/Lines/easy_sudokus.txt"));
@@ -1327,7 +3258,7 @@ fn print_grid_state(
 
     let mut lengths = [0; 3];
     for stack in 0..3 {
-        lengths[stack] = column_widths[stack * 3..][..3].iter().sum::<u8>() as usize;
+        lengths[stack] = column_widths[stack * 3..][..3].iter().sum::<usize>();
     }
     _print_separator(
         f,
@@ -1347,7 +3278,7 @@ fn print_grid_state(
                     let cell_state = grid_state[full_row * 9 + full_col];
                     match cell_state {
                         CellState::Digit(digit) => {
-                            write!(f, " {:<1$} ", digit.get(), column_widths[full_col] as usize)?
+                            write!(f, " {:<1$} ", digit.get(), column_widths[full_col])?
                         }
                         CellState::Candidates(cands) => {
                             write!(f, " ")?;
@@ -1392,6 +3323,79 @@ fn print_grid_state(
     )
 }
 
+fn print_candidate_grid(
+    f: &mut impl std::fmt::Write,
+    grid_state: [CellState; 81],
+    upper_left_corner: &str,
+    upper_right_corner: &str,
+    lower_left_corner: &str,
+    lower_right_corner: &str,
+
+    left_junction: &str,
+    right_junction: &str,
+    upper_junction: &str,
+    lower_junction: &str,
+    middle_junction: &str,
+
+    horizontal_bar: &str,
+    vertical_bar: &str,
+) -> Result<(), std::fmt::Error> {
+    // 3 cells of 3 characters each, separated and surrounded by spaces
+    let stack_width = 13;
+    let separator = |f: &mut dyn std::fmt::Write, left: &str, middle: &str, right: &str| {
+        let bar = horizontal_bar.repeat(stack_width);
+        writeln!(
+            f,
+            "{left}{bar}{middle}{bar}{middle}{bar}{right}",
+            left = left,
+            middle = middle,
+            right = right,
+            bar = bar
+        )
+    };
+
+    separator(f, upper_left_corner, upper_junction, upper_right_corner)?;
+    for row in 0..9 {
+        if row % 3 != 0 {
+            // empty line between the rows of a band for readability
+            let space = " ".repeat(stack_width);
+            writeln!(
+                f,
+                "{bar}{space}{bar}{space}{bar}{space}{bar}",
+                bar = vertical_bar,
+                space = space
+            )?;
+        }
+        for subrow in 0..3 {
+            write!(f, "{}", vertical_bar)?;
+            for col in 0..9 {
+                write!(f, " ")?;
+                for subcol in 0..3 {
+                    let keypad_digit = Digit::new(subrow * 3 + subcol + 1);
+                    match grid_state[row * 9 + col as usize] {
+                        CellState::Digit(digit) if subrow == 1 && subcol == 1 => {
+                            write!(f, "{}", digit.get())?
+                        }
+                        CellState::Digit(_) => write!(f, " ")?,
+                        CellState::Candidates(cands) if cands.contains(keypad_digit) => {
+                            write!(f, "{}", keypad_digit.get())?
+                        }
+                        CellState::Candidates(_) => write!(f, ".")?,
+                    }
+                }
+                if col % 3 == 2 {
+                    write!(f, " {}", vertical_bar)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        if row == 2 || row == 5 {
+            separator(f, left_junction, middle_junction, right_junction)?;
+        }
+    }
+    separator(f, lower_left_corner, lower_junction, lower_right_corner)
+}
+
 fn _print_separator(
     f: &mut std::fmt::Formatter,
     left_junction: &str,
@@ -3,24 +3,36 @@ pub(crate) mod prelude;
 pub(crate) mod almost_locked_sets;
 pub(crate) mod avoidable_rectangles;
 pub(crate) mod basic_fish;
+pub(crate) mod bug;
+pub(crate) mod empty_rectangle;
 pub(crate) mod hidden_singles;
 pub(crate) mod hidden_subsets;
 pub(crate) mod locked_candidates;
 pub(crate) mod mutant_fish;
 pub(crate) mod naked_singles;
 pub(crate) mod naked_subsets;
+pub(crate) mod remote_pairs;
+pub(crate) mod skyscraper;
+pub(crate) mod two_string_kite;
+pub(crate) mod unique_rectangles;
+pub(crate) mod w_wing;
+pub(crate) mod x_chain;
+pub(crate) mod xy_chain;
 pub(crate) mod xy_wing;
 pub(crate) mod xyz_wing;
 
-use super::StrategySolver;
+use super::{Difficulty, StrategySolver, Tier};
 use crate::helper::Unsolvable;
+use crate::{Solver, Sudoku};
 
 /// The strategies that can be used to find hints, solve or grade a sudoku.
 ///
 /// This can be used with [`StrategySolver::solve`].
 /// May be expanded in the future.
+///
+/// Strategies are ordered by their declaration, which groups related strategies together.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(missing_docs)]
 pub enum Strategy {
     NakedSingles,
@@ -35,11 +47,35 @@ pub enum Strategy {
     XWing,
     Swordfish,
     Jellyfish,
+    /// Two lines with 2 positions of a digit each, one of which is shared.
+    Skyscraper,
+    /// A row and a column with 2 positions of a digit each, one end of each in the same block.
+    TwoStringKite,
+    /// A block in which a digit is confined to one row and one column, combined with a conjugate pair.
+    EmptyRectangle,
     XyWing,
     XyzWing,
+    /// Two bivalue cells with the same candidates, connected by a strong link on one of them.
+    WWing,
+    /// X-wings with additional candidates in a single block, including sashimi x-wings.
+    FinnedXWing,
     MutantSwordfish,
     MutantJellyfish,
     AvoidableRectangles,
+    /// Unique rectangles of type 1 and 2.
+    /// Only valid for sudokus with a unique solution, so it must be opted into explicitly.
+    UniqueRectangles,
+    /// Bivalue universal grave + 1 (BUG+1).
+    /// Only valid for sudokus with a unique solution, so it must be opted into explicitly.
+    Bug,
+    /// Chains of bivalue cells with the same 2 candidates, which alternate between the cells.
+    RemotePairs,
+    /// XY-chains of up to 10 bivalue cells.
+    XyChain,
+    /// X-chains of up to 12 cells.
+    XChain,
+    /// X-chains of up to 12 nodes, which can be single cells or the cells of a miniline.
+    GroupedXChain,
     //SinglesChain,
 }
 
@@ -59,14 +95,186 @@ impl Strategy {
         Strategy::NakedTriples,     // 36
         Strategy::Swordfish,        // 38
         Strategy::HiddenTriples,    // 40
+        Strategy::Skyscraper,       // 40
+        Strategy::TwoStringKite,    // 40
         Strategy::XyWing,           // 42
         Strategy::XyzWing,          // 44
+        Strategy::FinnedXWing,      // not rated
         Strategy::NakedQuads,       // 50
         Strategy::Jellyfish,        // 52
         Strategy::HiddenQuads,      // 54
+        Strategy::XChain,           // not rated, searches chains of up to
+                                    // x_chain::MAX_LENGTH = 12 cells
         //Strategy::SinglesChain,
     ];
 
+    /// Returns the name of the strategy in snake case, e.g. `"naked_pairs"` or `"x_wing"`.
+    pub fn name(self) -> &'static str {
+        use self::Strategy::*;
+        match self {
+            NakedSingles => "naked_singles",
+            HiddenSingles => "hidden_singles",
+            LockedCandidates => "locked_candidates",
+            NakedPairs => "naked_pairs",
+            NakedTriples => "naked_triples",
+            NakedQuads => "naked_quads",
+            HiddenPairs => "hidden_pairs",
+            HiddenTriples => "hidden_triples",
+            HiddenQuads => "hidden_quads",
+            XWing => "x_wing",
+            Swordfish => "swordfish",
+            Jellyfish => "jellyfish",
+            Skyscraper => "skyscraper",
+            TwoStringKite => "two_string_kite",
+            EmptyRectangle => "empty_rectangle",
+            XyWing => "xy_wing",
+            XyzWing => "xyz_wing",
+            WWing => "w_wing",
+            FinnedXWing => "finned_x_wing",
+            MutantSwordfish => "mutant_swordfish",
+            MutantJellyfish => "mutant_jellyfish",
+            AvoidableRectangles => "avoidable_rectangles",
+            UniqueRectangles => "unique_rectangles",
+            Bug => "bug",
+            RemotePairs => "remote_pairs",
+            XyChain => "xy_chain",
+            XChain => "x_chain",
+            GroupedXChain => "grouped_x_chain",
+        }
+    }
+
+    /// Returns the [`Difficulty`] of sudokus that require this strategy.
+    ///
+    /// | Difficulty | Strategies |
+    /// |------------|------------|
+    /// | `Easy`     | naked singles, hidden singles |
+    /// | `Medium`   | locked candidates, naked and hidden pairs, triples and quads |
+    /// | `Hard`     | x-wing, finned x-wing, swordfish, jellyfish, skyscraper, two-string kite, empty rectangle |
+    /// | `Unfair`   | xy-wing, xyz-wing, w-wing, remote pairs, x-chains, grouped x-chains, xy-chains, avoidable rectangles, unique rectangles, bug+1 |
+    /// | `Extreme`  | mutant swordfish, mutant jellyfish |
+    pub fn difficulty(self) -> Difficulty {
+        use self::Strategy::*;
+        match self {
+            NakedSingles | HiddenSingles => Difficulty::Easy,
+            LockedCandidates | NakedPairs | NakedTriples | NakedQuads | HiddenPairs | HiddenTriples
+            | HiddenQuads => Difficulty::Medium,
+            XWing | FinnedXWing | Swordfish | Jellyfish | Skyscraper | TwoStringKite | EmptyRectangle => {
+                Difficulty::Hard
+            }
+            XyWing | XyzWing | WWing | RemotePairs | XChain | GroupedXChain | XyChain
+            | AvoidableRectangles | UniqueRectangles | Bug => Difficulty::Unfair,
+            MutantSwordfish | MutantJellyfish => Difficulty::Extreme,
+        }
+    }
+
+    /// Returns the [`Tier`] this strategy belongs to.
+    pub fn tier(self) -> Tier {
+        use self::Strategy::*;
+        match self {
+            NakedSingles | HiddenSingles | LockedCandidates => Tier::Basic,
+            NakedPairs | NakedTriples | NakedQuads | HiddenPairs | HiddenTriples | HiddenQuads => {
+                Tier::Intermediate
+            }
+            XWing | Swordfish | XyWing | XyzWing | WWing => Tier::Advanced,
+            Jellyfish | Skyscraper | TwoStringKite | EmptyRectangle | FinnedXWing | MutantSwordfish
+            | MutantJellyfish | AvoidableRectangles | UniqueRectangles | Bug | RemotePairs | XyChain
+            | XChain | GroupedXChain => Tier::Expert,
+        }
+    }
+
+    /// Looks up a strategy by its [`name`](Strategy::name).
+    /// Case, underscores, hyphens and spaces are ignored, so `"xwing"`, `"X-Wing"` and `"x_wing"`
+    /// all refer to [`Strategy::XWing`]. Returns `None` for unknown names and for strategies that aren't
+    /// implemented yet, like [`Strategy::AvoidableRectangles`].
+    pub fn from_name(name: &str) -> Option<Strategy> {
+        fn normalize(name: &str) -> impl Iterator<Item = char> + '_ {
+            name.chars()
+                .filter(|&ch| ch != '_' && ch != '-' && ch != ' ')
+                .map(|ch| ch.to_ascii_lowercase())
+        }
+
+        Self::ALL_VARIANTS
+            .iter()
+            .cloned()
+            .find(|strategy| normalize(strategy.name()).eq(normalize(name)))
+    }
+
+    /// The cost of each strategy in a [`Deductions::score`](super::deduction::Deductions::score).
+    /// Every strategy has exactly one entry. The weights grow with the [`difficulty`](Strategy::difficulty),
+    /// so a harder grade always costs more per deduction than an easier one.
+    #[rustfmt::skip]
+    pub const SCORE_WEIGHTS: &'static [(Strategy, u32)] = &[
+        (Strategy::NakedSingles,         1),
+        (Strategy::HiddenSingles,        1),
+        (Strategy::LockedCandidates,     3),
+        (Strategy::NakedPairs,           4),
+        (Strategy::NakedTriples,         6),
+        (Strategy::NakedQuads,           8),
+        (Strategy::HiddenPairs,          5),
+        (Strategy::HiddenTriples,        7),
+        (Strategy::HiddenQuads,          9),
+        (Strategy::XWing,               10),
+        (Strategy::Swordfish,           12),
+        (Strategy::Jellyfish,           16),
+        (Strategy::Skyscraper,          10),
+        (Strategy::TwoStringKite,       10),
+        (Strategy::EmptyRectangle,      11),
+        (Strategy::XyWing,              20),
+        (Strategy::XyzWing,             22),
+        (Strategy::WWing,               22),
+        (Strategy::FinnedXWing,         13),
+        (Strategy::MutantSwordfish,     40),
+        (Strategy::MutantJellyfish,     45),
+        (Strategy::AvoidableRectangles, 20),
+        (Strategy::UniqueRectangles,    20),
+        (Strategy::Bug,                 20),
+        (Strategy::RemotePairs,         21),
+        (Strategy::XyChain,             35),
+        (Strategy::XChain,              32),
+        (Strategy::GroupedXChain,       38),
+    ];
+
+    /// Returns the weight of this strategy in [`SCORE_WEIGHTS`](Self::SCORE_WEIGHTS).
+    pub fn score_weight(self) -> u32 {
+        Self::SCORE_WEIGHTS
+            .iter()
+            .find(|&&(strategy, _)| strategy == self)
+            .map(|&(_, weight)| weight)
+            .unwrap()
+    }
+
+    // all strategies that can be used, AvoidableRectangles is not implemented yet
+    #[rustfmt::skip]
+    const ALL_VARIANTS: &'static [Strategy] = &[
+        Strategy::NakedSingles,
+        Strategy::HiddenSingles,
+        Strategy::LockedCandidates,
+        Strategy::NakedPairs,
+        Strategy::NakedTriples,
+        Strategy::NakedQuads,
+        Strategy::HiddenPairs,
+        Strategy::HiddenTriples,
+        Strategy::HiddenQuads,
+        Strategy::XWing,
+        Strategy::Swordfish,
+        Strategy::Jellyfish,
+        Strategy::Skyscraper,
+        Strategy::TwoStringKite,
+        Strategy::EmptyRectangle,
+        Strategy::XyWing,
+        Strategy::XyzWing,
+        Strategy::WWing,
+        Strategy::FinnedXWing,
+        Strategy::MutantSwordfish,
+        Strategy::MutantJellyfish,
+        Strategy::UniqueRectangles,
+        Strategy::Bug,
+        Strategy::RemotePairs,
+        Strategy::XyChain,
+        Strategy::XChain,
+        Strategy::GroupedXChain,
+    ];
+
     // is_first_strategy is an optimization hint
     // it doesn't need to be used
     pub(crate) fn deduce(
@@ -77,7 +285,7 @@ impl Strategy {
     ) -> Result<(), Unsolvable> {
         use self::Strategy::*;
         match *self {
-            NakedSingles if !stop_after_first && is_first_strategy => {
+            NakedSingles if !stop_after_first && is_first_strategy && !state.explicit_singles => {
                 state._update_cell_poss_house_solved(true, true)
             }
             NakedSingles => state.find_naked_singles(stop_after_first),
@@ -92,10 +300,21 @@ impl Strategy {
             XWing => state.find_xwings(stop_after_first),
             Swordfish => state.find_swordfish(stop_after_first),
             Jellyfish => state.find_jellyfish(stop_after_first),
+            Skyscraper => state.find_skyscraper(stop_after_first),
+            TwoStringKite => state.find_two_string_kite(stop_after_first),
+            EmptyRectangle => state.find_empty_rectangle(stop_after_first),
             XyWing => state.find_xy_wing(stop_after_first),
             XyzWing => state.find_xyz_wing(stop_after_first),
+            WWing => state.find_w_wing(stop_after_first),
+            FinnedXWing => state.find_finned_xwings(stop_after_first),
             MutantSwordfish => state.find_mutant_fish(3, stop_after_first),
             MutantJellyfish => state.find_mutant_fish(4, stop_after_first),
+            UniqueRectangles => state.find_unique_rectangles(stop_after_first),
+            Bug => state.find_bug(),
+            RemotePairs => state.find_remote_pairs(stop_after_first),
+            XyChain => state.find_xy_chain(xy_chain::MAX_LENGTH, stop_after_first),
+            XChain => state.find_x_chain(false, x_chain::MAX_LENGTH, stop_after_first),
+            GroupedXChain => state.find_x_chain(true, x_chain::MAX_LENGTH, stop_after_first),
             //SinglesChain => state.find_singles_chain(stop_after_first), // TODO: Implement non-eager SinglesChain
             _ => unimplemented!(),
         }
@@ -113,3 +332,157 @@ impl Strategy {
         self.deduce(state, false, is_first_strategy)
     }
 }
+
+/// Solves with a [`StrategySolver`] restricted to these strategies. Returns `None`, if they
+/// aren't sufficient to solve the sudoku.
+impl Solver for &[Strategy] {
+    fn solve(self, sudoku: Sudoku) -> Option<Sudoku> {
+        StrategySolver::from_sudoku(sudoku)
+            .solve(self)
+            .ok()
+            .map(|(solution, _)| solution)
+    }
+}
+
+/// Parses a comma separated list of [strategy names](Strategy::from_name) like `"hidden_pairs,xwing,naked_triples"`.
+/// Whitespace around the names is ignored. Returns an error message naming the first unknown strategy.
+pub fn parse_strategy_list(list: &str) -> Result<Vec<Strategy>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Strategy::from_name(name).ok_or_else(|| format!("unknown strategy: {:?}", name)))
+        .collect()
+}
+
+/// The strategies that the tests of the more complex strategies take as given, from singles up to xyz-wings.
+/// They are listed explicitly, so that moving strategies between tiers doesn't change what those tests check.
+#[cfg(test)]
+pub(crate) const TEST_BASE_STRATEGIES: &[Strategy] = &[
+    Strategy::NakedSingles,
+    Strategy::HiddenSingles,
+    Strategy::LockedCandidates,
+    Strategy::NakedPairs,
+    Strategy::HiddenPairs,
+    Strategy::NakedTriples,
+    Strategy::HiddenTriples,
+    Strategy::NakedQuads,
+    Strategy::HiddenQuads,
+    Strategy::XWing,
+    Strategy::Swordfish,
+    Strategy::XyWing,
+    Strategy::XyzWing,
+];
+
+/// Returns the first deduction `strategy` finds in `sudoku` once the `base` strategies are stuck.
+/// Panics unless the `base` strategies alone can't solve `sudoku`, `strategy` finds something
+/// and both together solve it.
+#[cfg(test)]
+pub(crate) fn first_deduction(
+    sudoku: Sudoku,
+    base: &[Strategy],
+    strategy: Strategy,
+) -> crate::strategy::Deduction<Vec<crate::board::Candidate>> {
+    let mut solver = StrategySolver::from_sudoku(sudoku);
+    assert!(!solver.solve_in_place(base), "solvable without {:?}", strategy);
+
+    let deduction = solver
+        .preview(&[strategy], 1)
+        .get(0)
+        .unwrap_or_else(|| panic!("No {:?} found.", strategy))
+        .map_conflicts(<[crate::board::Candidate]>::to_vec);
+
+    let strategies = base.iter().cloned().chain(Some(strategy)).collect::<Vec<_>>();
+    assert!(solver.solve_in_place(&strategies));
+    assert_eq!(Some(solver.to_sudoku()), sudoku.solution());
+    deduction
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn strategy_names_roundtrip() {
+        for &strategy in Strategy::ALL_VARIANTS {
+            assert_eq!(Strategy::from_name(strategy.name()), Some(strategy));
+        }
+        assert_eq!(Strategy::from_name("X-Wing"), Some(Strategy::XWing));
+        assert_eq!(Strategy::from_name("x_wings"), None);
+        assert_eq!(Strategy::from_name("avoidable_rectangles"), None);
+    }
+
+    #[test]
+    fn named_strategies_are_solvable() {
+        let sudoku = Sudoku::from_str_line(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        for &strategy in Strategy::ALL_VARIANTS {
+            let strategy = Strategy::from_name(strategy.name()).unwrap();
+            // must not panic
+            let _ = StrategySolver::from_sudoku(sudoku).solve(&[strategy]);
+        }
+        assert!(parse_strategy_list("xwing, avoidable_rectangles").is_err());
+    }
+
+    #[test]
+    fn tiers_partition_strategies() {
+        let tiers = [Tier::Basic, Tier::Intermediate, Tier::Advanced, Tier::Expert];
+        // only valid for sudokus with a unique solution, so they aren't in any tier's list
+        let opt_in = [Strategy::UniqueRectangles, Strategy::Bug];
+        let n_strategies: usize = tiers.iter().map(|tier| tier.strategies().len()).sum();
+        assert_eq!(n_strategies + opt_in.len(), Strategy::ALL_VARIANTS.len());
+        for &tier in &tiers {
+            assert!(tier
+                .strategies()
+                .iter()
+                .all(|strategy| strategy.tier() == tier && !opt_in.contains(strategy)));
+        }
+    }
+
+    #[test]
+    fn tiers_are_solvable() {
+        let sudoku = Sudoku::from_str_line(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        for &tier in &[Tier::Basic, Tier::Intermediate, Tier::Advanced, Tier::Expert] {
+            // must not panic
+            let _ = StrategySolver::from_sudoku(sudoku).solve(tier.strategies());
+        }
+    }
+
+    #[test]
+    fn score_weights_follow_difficulty() {
+        for &strategy in Strategy::ALL_VARIANTS {
+            let n_weights = Strategy::SCORE_WEIGHTS
+                .iter()
+                .filter(|&&(weighted, _)| weighted == strategy)
+                .count();
+            assert_eq!(n_weights, 1);
+        }
+        for &strategy in Strategy::ALL_VARIANTS {
+            for &other in Strategy::ALL_VARIANTS {
+                if strategy.difficulty() < other.difficulty() {
+                    assert!(strategy.score_weight() < other.score_weight());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_strategy_lists() {
+        assert_eq!(
+            parse_strategy_list("hidden_pairs,xwing, naked_triples"),
+            Ok(vec![
+                Strategy::HiddenPairs,
+                Strategy::XWing,
+                Strategy::NakedTriples
+            ])
+        );
+        assert_eq!(parse_strategy_list(""), Ok(vec![]));
+        assert!(parse_strategy_list("naked_pairs,foo")
+            .unwrap_err()
+            .contains("foo"));
+    }
+}
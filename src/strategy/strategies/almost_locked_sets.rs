@@ -5,7 +5,7 @@ use super::prelude::*;
 pub(crate) fn find_almost_locked_sets(
     cells_poss_digits: &CellArray<Set<Digit>>,
     //house_solved_digits: &HouseArray<Set<Digit>>,
-    //subset_size: u8,
+    //subset_size: usize,
     stop_after_first: bool,
     mut on_subset: impl FnMut(
         //House,
@@ -97,7 +97,7 @@ pub(crate) fn _walk_combinations(
     digits: Set<Digit>,
     almost_locked_sets: &mut AlmostLockedSets,
     house: usize,
-    depth: u8,
+    depth: usize,
 ) {
     while let Some(cell) = cells.next() {
         let new_cell_set = cell_set | cell.as_set();
@@ -110,7 +110,7 @@ pub(crate) fn _walk_combinations(
         assert!(new_cell_set.len() == depth + 1);
 
         if new_digits.len() == new_cell_set.len() + 1 {
-            almost_locked_sets[new_cell_set.len() as usize - 1][house].push((new_cell_set, new_digits));
+            almost_locked_sets[new_cell_set.len() - 1][house].push((new_cell_set, new_digits));
         }
 
         _walk_combinations(
@@ -2,7 +2,7 @@ use super::prelude::*;
 
 pub(crate) fn find_fish(
     house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
-    max_size: u8,
+    max_size: usize,
     stop_after_first: bool,
     mut on_fish: impl FnMut(
         Set<Line>,           // all rows or all cols
@@ -17,11 +17,19 @@ pub(crate) fn find_fish(
                 house_poss_positions,
                 digit,
                 max_size,
+                max_size,
                 Set::NONE,
                 lines.into_iter(),
-                lines,
                 Set::NONE,
-                &mut on_fish,
+                &mut |line_set, union_poss_pos| {
+                    // nothing of interest found
+                    if union_poss_pos.len() != max_size {
+                        return false;
+                    }
+
+                    // found xwing, swordfish, or jellyfish
+                    on_fish(lines, digit, line_set, union_poss_pos)
+                },
                 stop_after_first,
             ) {
                 return Ok(());
@@ -31,43 +39,125 @@ pub(crate) fn find_fish(
     Ok(())
 }
 
+// A finned fish is a basic fish with additional candidates, the fins, in its base lines.
+// All fins must be in the same block. Either the fish or one of the fins is true, so
+// only cells of the cover lines that are also in the block of the fins can be eliminated.
+//
+// Every base line must contain at least 1 position of the fish itself. If it contains only 1,
+// because fins replace the other ones, the fish is a sashimi fish. The same eliminations apply.
+pub(crate) fn find_finned_fish(
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    max_size: usize,
+    stop_after_first: bool,
+    mut on_fish: impl FnMut(
+        Set<Line>,           // all rows or all cols
+        Digit,               //
+        Set<Line>,           // base
+        Set<Position<Line>>, // cover
+        Set<Cell>,           // fins
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for digit in (1..10).map(Digit::new) {
+        for &lines in &[Line::ALL_ROWS, Line::ALL_COLS] {
+            // With 3 fin positions in a block, no cover line could pass through it
+            // and nothing could be eliminated.
+            let max_positions = max_size + 2;
+            if basic_fish_walk_combinations(
+                house_poss_positions,
+                digit,
+                max_size,
+                max_positions,
+                Set::NONE,
+                lines.into_iter(),
+                Set::NONE,
+                &mut |line_set, union_poss_pos| {
+                    if union_poss_pos.len() == max_size {
+                        return false;
+                    }
+                    let line_positions =
+                        |line: Line| Set::<Position<Line>>::from(house_poss_positions[line][digit]);
+
+                    for fin_positions in
+                        fin_position_combinations(union_poss_pos, union_poss_pos.len() - max_size)
+                    {
+                        let cover = union_poss_pos.without(fin_positions);
+                        let is_finned_fish = line_set
+                            .into_iter()
+                            .all(|line| line_positions(line).overlaps(cover));
+                        if !is_finned_fish {
+                            continue;
+                        }
+
+                        let fins = line_set
+                            .into_iter()
+                            .map(|line| line.cells_at(line_positions(line) & fin_positions))
+                            .fold(Set::NONE, std::ops::BitOr::bitor);
+                        let fin_block = fins.into_iter().next().unwrap().block();
+                        if fins.without(fin_block.cells()).is_empty()
+                            && on_fish(lines, digit, line_set, cover, fins)
+                            && stop_after_first
+                        {
+                            return true;
+                        }
+                    }
+                    false
+                },
+                stop_after_first,
+            ) {
+                return Ok(());
+            };
+        }
+    }
+    Ok(())
+}
+
+// All subsets of `positions` with exactly `n_fins` elements, for 1 or 2 fins.
+fn fin_position_combinations(
+    positions: Set<Position<Line>>,
+    n_fins: usize,
+) -> impl Iterator<Item = Set<Position<Line>>> {
+    positions.into_iter().flat_map(move |first| {
+        let singles = Some(first.as_set()).filter(|_| n_fins == 1);
+        let pairs = positions
+            .into_iter()
+            .filter(move |&second| n_fins == 2 && second.get() > first.get())
+            .map(move |second| first.as_set() | second);
+        singles.into_iter().chain(pairs)
+    })
+}
+
 //             goal_depth
 // <degenerated>   1 (basically a naked/hidden single, not supported by this fn)
 // x-wing          2
 // swordfish       3
 // jellyfish       4
+//
+// `max_positions` bounds the size of the union of positions in the lines.
+// It is `goal_depth` for basic fish and higher for fish with fins.
 fn basic_fish_walk_combinations(
     house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
     digit: Digit,
-    goal_depth: u8,
+    goal_depth: usize,
+    max_positions: usize,
     line_set: Set<Line>,
     lines: SetIter<Line>,
-    all_lines: Set<Line>,
     union_poss_pos: Set<Position<Line>>,
-    on_fish: &mut impl FnMut(Set<Line>, Digit, Set<Line>, Set<Position<Line>>) -> bool,
+    on_combination: &mut impl FnMut(Set<Line>, Set<Position<Line>>) -> bool,
     stop_after_first: bool,
 ) -> bool {
     if line_set.len() == goal_depth {
-        // nothing of interest found
-        if union_poss_pos.len() != goal_depth {
-            return false;
-        }
-
-        // found xwing, swordfish, or jellyfish
-        if on_fish(all_lines, digit, line_set, union_poss_pos) {
-            return true;
-        }
+        return on_combination(line_set, union_poss_pos);
     }
 
     let mut lines = lines;
     while let Some(line) = lines.next() {
-        let possible_pos = house_poss_positions[line][digit];
+        let possible_pos = Set::<Position<Line>>::from(house_poss_positions[line][digit]);
         let n_poss = possible_pos.len();
-        let new_union_poss_pos = union_poss_pos | possible_pos.as_line_set();
+        let new_union_poss_pos = union_poss_pos | possible_pos;
 
         // n_poss == 0 => solved row (or impossible)
         // n_poss == 1 => hidden single
-        if n_poss < 2 || new_union_poss_pos.len() > goal_depth as u8 {
+        if n_poss < 2 || new_union_poss_pos.len() > max_positions {
             continue;
         }
 
@@ -76,11 +166,11 @@ fn basic_fish_walk_combinations(
             house_poss_positions,
             digit,
             goal_depth,
+            max_positions,
             new_line_set,
             lines.clone(),
-            all_lines,
             new_union_poss_pos,
-            on_fish,
+            on_combination,
             stop_after_first,
         ) {
             return true;
@@ -88,3 +178,37 @@ fn basic_fish_walk_combinations(
     }
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn sashimi_xwing() {
+        let sudoku = Sudoku::from_str_line(
+            ".8.4..6....3..8..71........26.1..7....7.4.1....9..3.68........48..3..2....4..9.3.",
+        )
+        .unwrap();
+        // the rest of this sudoku also needs w-wings
+        let base = [TEST_BASE_STRATEGIES, &[Strategy::WWing]].concat();
+        // rows 2 and 9 in columns 5 and 9, but row 2 has the 1 only in column 5 and in the fin r2c8
+        match first_deduction(sudoku, &base, Strategy::FinnedXWing) {
+            Deduction::FinnedFish {
+                lines,
+                digit,
+                positions,
+                fin_cells,
+                conflicts,
+            } => {
+                assert_eq!(lines, Line::new(1).as_set() | Line::new(8));
+                assert_eq!(digit, Digit::new(1));
+                assert_eq!(positions, Position::<Line>::new(4).as_set() | Position::new(8));
+                assert_eq!(fin_cells, Cell::new(16).as_set());
+                assert_eq!(conflicts, [Candidate::new(8, 1)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
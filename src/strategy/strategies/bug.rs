@@ -0,0 +1,95 @@
+use super::prelude::*;
+
+// A bivalue universal grave (BUG) is a state in which every unsolved cell has exactly 2 candidates
+// and every candidate appears exactly twice in each house. Such a grid has either no or at least 2
+// solutions, so it can't occur in a sudoku with a unique solution.
+//
+// In a BUG+1, there is a single cell with 3 candidates, one of which appears 3 times in each of its houses,
+// while all other candidates appear exactly twice per house. Without this digit, the grid would be a BUG,
+// so the cell must contain it.
+pub(crate) fn find_bug(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    mut on_bug: impl FnMut(
+        Cell,  // the cell with 3 candidates
+        Digit, // the digit it must contain
+    ),
+) -> Result<(), Unsolvable> {
+    // solved cells have no candidates
+    let mut trivalue_cell = None;
+    for cell in Cell::all() {
+        match cells_poss_digits[cell].len() {
+            0 | 2 => (),
+            3 if trivalue_cell.is_none() => trivalue_cell = Some(cell),
+            _ => return Ok(()),
+        }
+    }
+    let trivalue_cell = match trivalue_cell {
+        Some(cell) => cell,
+        None => return Ok(()),
+    };
+
+    let mut house_digit_counts = HouseArray([DigitArray([0u8; 9]); 27]);
+    for cell in Cell::all() {
+        for digit in cells_poss_digits[cell] {
+            for &house in cell.houses().iter() {
+                house_digit_counts[house][digit] += 1;
+            }
+        }
+    }
+
+    let trivalue_houses = trivalue_cell
+        .houses()
+        .iter()
+        .fold(Set::NONE, |houses, &house| houses | house);
+    let mut bug_digits = cells_poss_digits[trivalue_cell]
+        .into_iter()
+        .filter(|&digit| house_digit_counts[trivalue_cell.row()][digit] == 3);
+    let bug_digit = match (bug_digits.next(), bug_digits.next()) {
+        (Some(digit), None) => digit,
+        _ => return Ok(()),
+    };
+
+    for house in House::all() {
+        for digit in Set::<Digit>::ALL {
+            let count = house_digit_counts[house][digit];
+            let is_valid = match trivalue_houses.contains(house) && digit == bug_digit {
+                true => count == 3,
+                false => count == 0 || count == 2,
+            };
+            if !is_valid {
+                return Ok(());
+            }
+        }
+    }
+
+    on_bug(trivalue_cell, bug_digit);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn bug_plus_one() {
+        let sudoku = Sudoku::from_str_line(
+            "5.3.........3.1.86...6...3.8..1...5.97..6..48.4...8..9.3...6...68.9.2.........4.3",
+        )
+        .unwrap();
+        // all unsolved cells but r1c7 are bivalue and its 2 appears 3 times in row 1, column 7 and block 3
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::Bug) {
+            Deduction::Bug {
+                cell,
+                digit,
+                conflicts,
+            } => {
+                assert_eq!(cell, Cell::new(6));
+                assert_eq!(digit, Digit::new(2));
+                assert_eq!(conflicts, [Candidate::new(6, 7), Candidate::new(6, 9)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::prelude::*;
+
+// An empty rectangle is a block in which a digit is confined to the cells of one row and one column,
+// with at least one position outside of their intersection in each of them. The digit must therefore be
+// in the row or the column, so it behaves like a strong link between them.
+// If a conjugate pair in a line crossing the row outside of the block has one end in the row, then
+// the digit can't be where the other end's parallel to the row meets the column. There, it would force
+// the conjugate pair onto the end in the row and leave no place for the digit in the block.
+// The same applies to conjugate pairs in lines crossing the column.
+pub(crate) fn find_empty_rectangle(
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    stop_after_first: bool,
+    mut on_empty_rectangle: impl FnMut(
+        Digit, //
+        Block, //
+        House, // the line containing the conjugate pair
+        Cell,  // the cell the digit can't be in
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for digit in (1..10).map(Digit::new) {
+        let conjugate_pair = |house: House| {
+            let positions = house_poss_positions[house][digit];
+            match positions.len() {
+                2 => Some(house.cells_at(positions)),
+                _ => None,
+            }
+        };
+
+        for block in Block::all() {
+            let block_cells = House::from(block).cells_at(house_poss_positions[House::from(block)][digit]);
+
+            for row in Row::all().filter(|row| row.cells().overlaps(block.cells())) {
+                for col in Col::all().filter(|col| col.cells().overlaps(block.cells())) {
+                    let (row_cells, col_cells) = (row.cells(), col.cells());
+                    // the digit must be confined to the cross and be on both of its arms,
+                    // otherwise it would be confined to a single line
+                    if !block_cells.without(row_cells | col_cells).is_empty()
+                        || block_cells.without(col_cells).is_empty()
+                        || block_cells.without(row_cells).is_empty()
+                    {
+                        continue;
+                    }
+
+                    // conjugate pairs in columns crossing the row and in rows crossing the column
+                    for &(lines, crossed_cells, other_cells) in &[
+                        (Line::ALL_COLS, row_cells, col_cells),
+                        (Line::ALL_ROWS, col_cells, row_cells),
+                    ] {
+                        for house in lines.into_iter().map(House::from) {
+                            if house.cells().overlaps(block.cells()) {
+                                continue;
+                            }
+                            let pair = match conjugate_pair(house) {
+                                Some(pair) if pair.overlaps(crossed_cells) => pair,
+                                _ => continue,
+                            };
+                            // the other line through the other end runs parallel to the crossed one
+                            let other_end = pair.without(crossed_cells).one_possibility();
+                            let conflict_cell =
+                                (other_end.row().cells() | other_end.col().cells()) & other_cells;
+                            let conflict_cell = conflict_cell.one_possibility();
+                            if block.cells().contains(conflict_cell) {
+                                continue;
+                            }
+
+                            if on_empty_rectangle(digit, block, house, conflict_cell) && stop_after_first {
+                                return Ok(());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn empty_rectangle() {
+        let sudoku = Sudoku::from_str_line(
+            "......5.3...5.7.9...1.3264.4....3..6....1....6..8....2.4615.3...7.3.6...3.8......",
+        )
+        .unwrap();
+        // box 1 confines the 2 to row 1 and column 1, column 8 can only have it in r1c8 and r7c8
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::EmptyRectangle) {
+            Deduction::EmptyRectangle {
+                block,
+                digit,
+                conjugate_house,
+                conflicts,
+            } => {
+                assert_eq!(block, Block::new(0));
+                assert_eq!(digit, Digit::new(2));
+                assert_eq!(conjugate_house, House::from(Col::new(7)));
+                assert_eq!(conflicts, [Candidate::new(54, 2)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
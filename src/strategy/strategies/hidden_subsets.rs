@@ -3,7 +3,8 @@ use super::prelude::*;
 pub(crate) fn find_hidden_subsets(
     house_solved_digits: &HouseArray<Set<Digit>>,
     house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
-    subset_size: u8,
+    houses: impl IntoIterator<Item = House>,
+    subset_size: usize,
     stop_after_first: bool,
     mut on_subset: impl FnMut(House, Set<Digit>, Set<Position<House>>) -> bool,
 ) -> Result<(), Unsolvable> {
@@ -14,7 +15,7 @@ pub(crate) fn find_hidden_subsets(
         house: House,
         digit_set: Set<Digit>,
         on_subset: &mut impl FnMut(House, Set<Digit>, Set<Position<House>>) -> bool,
-        subset_size: u8,
+        subset_size: usize,
         stop_after_first: bool,
     ) -> bool {
         // subsets of 5 and more numbers always have complementary subsets
@@ -55,7 +56,7 @@ pub(crate) fn find_hidden_subsets(
         false
     }
 
-    for house in House::all() {
+    for house in houses {
         if house_solved_digits[house].is_full() {
             continue;
         }
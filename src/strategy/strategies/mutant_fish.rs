@@ -4,7 +4,7 @@ use super::prelude::*;
 
 pub(crate) fn find_mutant_fish(
     house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
-    target_size: u8,
+    target_size: usize,
     stop_after_first: bool,
     mut on_fish: impl FnMut(
         Digit,
@@ -33,7 +33,7 @@ pub(crate) fn find_mutant_fish(
 fn find_base(
     house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
     digit: Digit,
-    target_size: u8,
+    target_size: usize,
     base_houses: Set<House>,
     houses: SetIter<House>,
     candidate_cells: Set<Cell>,
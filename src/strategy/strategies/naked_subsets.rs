@@ -3,7 +3,8 @@ use super::prelude::*;
 pub(crate) fn find_naked_subsets(
     cells_poss_digits: &CellArray<Set<Digit>>,
     house_solved_digits: &HouseArray<Set<Digit>>,
-    subset_size: u8,
+    houses: impl IntoIterator<Item = House>,
+    subset_size: usize,
     stop_after_first: bool,
     mut on_subset: impl FnMut(House, Set<Position<House>>, Set<Digit>) -> bool,
 ) -> Result<(), Unsolvable> {
@@ -14,7 +15,7 @@ pub(crate) fn find_naked_subsets(
         house: House,
         position_set: Set<Position<House>>,
         on_subset: &mut impl FnMut(House, Set<Position<House>>, Set<Digit>) -> bool,
-        subset_size: u8,
+        subset_size: usize,
         stop_after_first: bool,
     ) -> bool {
         // subsets of 5 and more numbers always have complementary subsets
@@ -60,7 +61,7 @@ pub(crate) fn find_naked_subsets(
         false
     }
 
-    for house in House::all() {
+    for house in houses {
         if house_solved_digits[house].is_full() {
             continue;
         }
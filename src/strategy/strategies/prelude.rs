@@ -9,6 +9,7 @@ pub(crate) use crate::board::{
         Cell,
         Row,
         Col,
+        Block,
         Line,
         Chute,
         MiniLine,
@@ -0,0 +1,77 @@
+use super::prelude::*;
+
+// A remote pairs chain consists of bivalue cells with the same 2 candidates, in which each cell sees the next one.
+// Neighbouring cells must contain different digits, so the cells can be colored alternately and all cells of
+// one color contain the same digit. A cell that sees cells of both colors can therefore contain neither digit.
+// Chains of 2 or 3 cells only eliminate like naked pairs.
+pub(crate) fn find_remote_pairs(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    stop_after_first: bool,
+    mut on_remote_pairs: impl FnMut(
+        Set<Digit>,     // the candidates of all cells in the chain
+        [Set<Cell>; 2], // the cells of each color
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    let mut colored = Set::NONE;
+    for start in Cell::all() {
+        let digits = cells_poss_digits[start];
+        if digits.len() != 2 || colored.contains(start) {
+            continue;
+        }
+        let pair_cells = Cell::all()
+            .filter(|&cell| cells_poss_digits[cell] == digits)
+            .fold(Set::NONE, |cells, cell| cells | cell);
+
+        // color all cells connected to `start` by a depth first search
+        let mut colors = [start.as_set(), Set::NONE];
+        let mut stack = vec![(start, 0)];
+        while let Some((cell, color)) = stack.pop() {
+            let other_color = 1 - color;
+            // two cells of the same color that see each other would have to contain the same digit
+            if cell.peer_set().overlaps(colors[color]) {
+                return Err(Unsolvable);
+            }
+            let neighbors = (cell.peer_set() & pair_cells).without(colors[0] | colors[1]);
+            for neighbor in neighbors {
+                colors[other_color] |= neighbor;
+                stack.push((neighbor, other_color));
+            }
+        }
+        let chain_cells = colors[0] | colors[1];
+        colored |= chain_cells;
+
+        if chain_cells.len() >= 4 && on_remote_pairs(digits, colors) && stop_after_first {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn remote_pairs() {
+        let sudoku = Sudoku::from_str_line(
+            "...68..9.1.....3.8...3.5....9...7..38.75.16.96..8...5....9.6...9.6.....2.3..28...",
+        )
+        .unwrap();
+        // 7 cells with the candidates 2 and 4, r4c3 sees r1c3 and r5c2 of different colors
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::RemotePairs) {
+            Deduction::RemotePairs {
+                cells,
+                digits,
+                conflicts,
+            } => {
+                assert_eq!(cells.len(), 7);
+                assert!(cells.contains(Cell::new(2)) && cells.contains(Cell::new(37)));
+                assert_eq!(digits, Digit::new(2).as_set() | Digit::new(4));
+                assert_eq!(conflicts, [Candidate::new(29, 2), Candidate::new(29, 4)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
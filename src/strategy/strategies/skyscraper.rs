@@ -0,0 +1,71 @@
+use super::prelude::*;
+
+// A skyscraper consists of two parallel lines in each of which a digit has exactly 2 positions.
+// One of these positions is the same in both lines, the other ones differ. The digit can't be in both
+// cells at the shared position, so it must be in at least one of the other two, the ends.
+// It can therefore be removed from all cells that see both ends.
+// If both positions were shared, this would be an x-wing.
+pub(crate) fn find_skyscraper(
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    stop_after_first: bool,
+    mut on_skyscraper: impl FnMut(
+        Digit,     //
+        Set<Line>, // base lines
+        [Cell; 2], // ends
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for digit in (1..10).map(Digit::new) {
+        for &lines in &[Line::ALL_ROWS, Line::ALL_COLS] {
+            let line_positions = |line: Line| Set::<Position<Line>>::from(house_poss_positions[line][digit]);
+            let base_lines = lines
+                .into_iter()
+                .filter(|&line| line_positions(line).len() == 2)
+                .collect::<Vec<_>>();
+
+            for (i, &line1) in base_lines.iter().enumerate() {
+                for &line2 in &base_lines[i + 1..] {
+                    let (positions1, positions2) = (line_positions(line1), line_positions(line2));
+                    let shared = positions1 & positions2;
+                    if shared.len() != 1 {
+                        continue;
+                    }
+
+                    let end1 = line1.cells_at(positions1.without(shared)).one_possibility();
+                    let end2 = line2.cells_at(positions2.without(shared)).one_possibility();
+                    if on_skyscraper(digit, line1.as_set() | line2, [end1, end2]) && stop_after_first {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn skyscraper() {
+        let sudoku = Sudoku::from_str_line(
+            ".8..53........8.6...362.1.7.31......7.......3......92.5.9.728...7.9........84..1.",
+        )
+        .unwrap();
+        // rows 1 and 7, which share a position of the 1
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::Skyscraper) {
+            Deduction::Skyscraper {
+                base_lines,
+                digit,
+                conflicts,
+            } => {
+                assert_eq!(base_lines, Line::new(0).as_set() | Line::new(6));
+                assert_eq!(digit, Digit::new(1));
+                assert_eq!(conflicts, [Candidate::new(10, 1), Candidate::new(63, 1)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::prelude::*;
+
+// A two-string kite consists of a row and a column in each of which a digit has exactly 2 positions.
+// One end of the row and one end of the column lie in the same block, but in different cells,
+// so the digit can be in at most one of them. It must therefore be in at least one of the other two ends,
+// and it can be removed from all cells that see both of them.
+pub(crate) fn find_two_string_kite(
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    stop_after_first: bool,
+    mut on_two_string_kite: impl FnMut(
+        Digit,     //
+        Row,       //
+        Col,       //
+        [Cell; 2], // the ends outside of the shared block
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for digit in (1..10).map(Digit::new) {
+        let cells_of = |house: House| {
+            let positions = house_poss_positions[house][digit];
+            match positions.len() {
+                2 => Some(house.cells_at(positions)),
+                _ => None,
+            }
+        };
+
+        for row in Row::all() {
+            let row_cells = match cells_of(House::from(row)) {
+                Some(cells) => cells,
+                None => continue,
+            };
+            for col in Col::all() {
+                let col_cells = match cells_of(House::from(col)) {
+                    Some(cells) => cells,
+                    None => continue,
+                };
+                // the ends in the shared block must be different cells
+                if row_cells.overlaps(col_cells) {
+                    continue;
+                }
+
+                for row_end in row_cells {
+                    for col_end in col_cells {
+                        let block = row_end.block();
+                        if col_end.block() != block {
+                            continue;
+                        }
+                        let other_row_end = row_cells.without(row_end.as_set()).one_possibility();
+                        let other_col_end = col_cells.without(col_end.as_set()).one_possibility();
+                        if other_row_end.block() == block || other_col_end.block() == block {
+                            continue;
+                        }
+
+                        if on_two_string_kite(digit, row, col, [other_row_end, other_col_end])
+                            && stop_after_first
+                        {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn two_string_kite() {
+        let sudoku = Sudoku::from_str_line(
+            "..3.2.1.5......27...715..34..82......4.....2......13..13..829...69......7.4.9.5..",
+        )
+        .unwrap();
+        // row 3 and column 3 with one end each in box 1
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::TwoStringKite) {
+            Deduction::TwoStringKite {
+                row,
+                col,
+                digit,
+                conflicts,
+            } => {
+                assert_eq!(row, Row::new(2));
+                assert_eq!(col, Col::new(2));
+                assert_eq!(digit, Digit::new(6));
+                assert_eq!(conflicts, [Candidate::new(42, 6)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
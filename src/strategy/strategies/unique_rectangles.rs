@@ -0,0 +1,157 @@
+use super::prelude::*;
+
+// A unique rectangle is made up of 4 unsolved cells in 2 rows, 2 columns and exactly 2 blocks.
+// If all 4 cells could only contain the same 2 digits, the digits could be swapped
+// and the sudoku would have 2 solutions (deadly pattern). For sudokus with a unique solution,
+// this must therefore be avoided.
+//
+// The floor of the rectangle are 2 cells in one line that contain only the 2 digits,
+// the roof are the other 2 cells, at least one of which contains additional candidates.
+pub(crate) fn find_unique_rectangles(
+    cell_poss_digits: &CellArray<Set<Digit>>,
+    stop_after_first: bool,
+    mut on_unique_rectangle: impl FnMut(
+        [Cell; 2],  // floor
+        [Cell; 2],  // roof
+        Set<Digit>, // digits of the deadly pattern
+        Set<Cell>,  // cells in which ..
+        Set<Digit>, // .. these digits are impossible
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for &transposed in &[false, true] {
+        // floor and roof are rows in the first pass and columns in the second
+        let cell = |line: u8, pos: u8| match transposed {
+            false => Cell::new(line * 9 + pos),
+            true => Cell::new(pos * 9 + line),
+        };
+
+        for floor_line in 0..9 {
+            for pos1 in 0..8 {
+                for pos2 in pos1 + 1..9 {
+                    let floor = [cell(floor_line, pos1), cell(floor_line, pos2)];
+                    let digits = cell_poss_digits[floor[0]];
+                    if digits.len() != 2 || cell_poss_digits[floor[1]] != digits {
+                        continue;
+                    }
+
+                    let positions_in_same_chute = pos1 / 3 == pos2 / 3;
+                    for roof_line in (0..9).filter(|&line| line != floor_line) {
+                        // the rectangle must occupy exactly 2 blocks
+                        let lines_in_same_chute = floor_line / 3 == roof_line / 3;
+                        if !(positions_in_same_chute ^ lines_in_same_chute) {
+                            continue;
+                        }
+
+                        let roof = [cell(roof_line, pos1), cell(roof_line, pos2)];
+                        let roof_digits = [cell_poss_digits[roof[0]], cell_poss_digits[roof[1]]];
+                        if !roof_digits[0].contains(digits) || !roof_digits[1].contains(digits) {
+                            continue;
+                        }
+
+                        // type 1: only one cell has extra candidates
+                        // it must contain one of them, so the 2 digits are impossible in it
+                        // 3 cells contain only the 2 digits, so the rectangle has such a floor in a row and
+                        // in a column. It's enough to look for the one in a row.
+                        let extra_cells = roof
+                            .iter()
+                            .zip(roof_digits.iter())
+                            .filter(|&(_, &cell_digits)| cell_digits != digits)
+                            .fold(Set::NONE, |cells, (&cell, _)| cells | cell);
+                        if extra_cells.len() == 1 && !transposed {
+                            if on_unique_rectangle(floor, roof, digits, extra_cells, digits)
+                                && stop_after_first
+                            {
+                                return Ok(());
+                            }
+                            continue;
+                        }
+
+                        // type 2: both roof cells have the same, single extra candidate
+                        // one of them must contain it, so it's impossible in all cells seeing both
+                        if roof_digits[0] != roof_digits[1] {
+                            continue;
+                        }
+                        if let Ok(Some(extra)) = roof_digits[0].without(digits).unique() {
+                            let conflict_cells = roof[0].peer_set() & roof[1].peer_set();
+                            if on_unique_rectangle(floor, roof, digits, conflict_cells, extra.as_set())
+                                && stop_after_first
+                            {
+                                return Ok(());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::{Candidate, Cell, Digit};
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+    use crate::Sudoku;
+
+    #[test]
+    fn unique_rectangle_type1() {
+        let sudoku = Sudoku::from_str_line(
+            "2.......9....148....5.9.67.7..56......6...5......87..1.82.4.1....935....6.......4",
+        )
+        .unwrap();
+        // r3c1, r3c2 and r8c1 contain only 1 and 4, so r8c2 must contain something else
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::UniqueRectangles) {
+            Deduction::UniqueRectangle {
+                floor,
+                roof,
+                digits,
+                conflicts,
+            } => {
+                assert_eq!(floor, Cell::new(18).as_set() | Cell::new(19));
+                assert_eq!(roof, Cell::new(63).as_set() | Cell::new(64));
+                assert_eq!(digits, Digit::new(1).as_set() | Digit::new(4));
+                assert_eq!(conflicts, [Candidate::new(64, 1), Candidate::new(64, 4)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn unique_rectangle_type2() {
+        // not solvable with the other strategies
+        let sudoku = Sudoku::from_str_line(
+            "9.54...1...6.....5.8.597...8.....6.9...2.5...5.7.....3...154.9.4.....3...1...35.2",
+        )
+        .unwrap();
+        let mut strategies = Strategy::ALL.to_vec();
+        assert!(StrategySolver::from_sudoku(sudoku).solve(&strategies).is_err());
+
+        strategies.push(Strategy::UniqueRectangles);
+        let (solution, deductions) = StrategySolver::from_sudoku(sudoku).solve(&strategies).unwrap();
+        assert_eq!(Some(solution), sudoku.solution());
+
+        let deduction = deductions
+            .iter()
+            .find(|deduction| deduction.strategy() == Strategy::UniqueRectangles)
+            .unwrap();
+        if let Deduction::UniqueRectangle {
+            floor,
+            roof,
+            digits,
+            conflicts,
+        } = deduction
+        {
+            assert_eq!((floor.len(), roof.len(), digits.len()), (2, 2, 2));
+            assert!(!conflicts.is_empty());
+            for conflict in conflicts {
+                assert!(roof
+                    .into_iter()
+                    .all(|cell| conflict.cell.peer_set().contains(cell)));
+                assert!(!digits.contains(conflict.digit));
+            }
+        } else {
+            unreachable!();
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::prelude::*;
+
+// A w-wing consists of two bivalue cells with the same candidates x and y that don't share a house
+// and a house in which y can only be in cells that see one of them each.
+// If neither of the bivalue cells contained x, both would contain y, leaving no place for y in the house.
+// One of them therefore contains x and it can be removed from all cells that see both.
+pub(crate) fn find_w_wing(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    stop_after_first: bool,
+    mut on_w_wing: impl FnMut(
+        [Cell; 2], // the bivalue cells
+        House,     // the house connecting them by a strong link on the other digit
+        Digit,     // the digit that one of the bivalue cells must contain
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    for cell1 in Cell::all() {
+        let digits = cells_poss_digits[cell1];
+        if digits.len() != 2 {
+            continue;
+        }
+
+        // cells in the same house as cell1 would form a naked pair
+        let partners = Cell::all()
+            .skip(cell1.as_index() + 1)
+            .filter(|&cell2| cells_poss_digits[cell2] == digits && !cell1.peer_set().contains(cell2));
+
+        for cell2 in partners {
+            let wing_cells = cell1.as_set() | cell2;
+            for link_digit in digits {
+                let digit = digits.without(link_digit.as_set()).unique().unwrap().unwrap();
+
+                // every connecting house allows the same eliminations, so only the first one is reported
+                let connector_house = House::all().find(|&house| {
+                    let positions = house_poss_positions[house][link_digit];
+                    if positions.len() != 2 {
+                        return false;
+                    }
+                    let link_cells = house.cells_at(positions);
+                    if link_cells.overlaps(wing_cells) {
+                        return false;
+                    }
+                    let mut link_cells = link_cells.into_iter();
+                    let (link1, link2) = (link_cells.next().unwrap(), link_cells.next().unwrap());
+                    let sees = |cell: Cell, other: Cell| cell.peer_set().contains(other);
+                    sees(link1, cell1) && sees(link2, cell2) || sees(link1, cell2) && sees(link2, cell1)
+                });
+
+                if let Some(house) = connector_house {
+                    if on_w_wing([cell1, cell2], house, digit) && stop_after_first {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn w_wing() {
+        let sudoku = Sudoku::from_str_line(
+            "...9.5..6..3.7........1.79..5.....1..143.827..6.....4..37.9........5.8..8..1.6...",
+        )
+        .unwrap();
+        // r2c6 and r3c9 are connected by the strong link on their other digit in row 7
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::WWing) {
+            Deduction::WWing {
+                cells,
+                connector_house,
+                digit,
+                conflicts,
+            } => {
+                assert_eq!(cells, Cell::new(14).as_set() | Cell::new(26));
+                assert_eq!(connector_house, House::new(6));
+                assert_eq!(digit, Digit::new(4));
+                assert_eq!(conflicts, [Candidate::new(21, 4)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use super::prelude::*;
+use crate::board::positions::MiniLine;
+
+/// Maximum number of nodes in the chains that [`Strategy::XChain`](crate::strategy::Strategy::XChain)
+/// and [`Strategy::GroupedXChain`](crate::strategy::Strategy::GroupedXChain) search for.
+pub(crate) const MAX_LENGTH: usize = 12;
+
+// An x-chain is a sequence of nodes for a single digit that are alternately connected by strong and weak links,
+// starting and ending with a strong one. A node is a single cell or, in grouped chains, the cells of a miniline
+// that contain the digit. Two nodes form a strong link, if they contain all positions of the digit in a house,
+// so if one of them doesn't contain the digit, the other one must. Two nodes form a weak link, if all of their
+// cells see each other, so if one contains the digit, the other one can't.
+// If the first node doesn't contain the digit, the second one must, so the third one can't and so on until the
+// last one, which must contain the digit. The digit can therefore be removed from all cells that see every cell of
+// the first and of the last node.
+//
+// Chains are searched from short to long, so each pair of end nodes is reported once with its shortest chain.
+pub(crate) fn find_x_chain(
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    grouped: bool,
+    max_length: usize,
+    stop_after_first: bool,
+    mut on_x_chain: impl FnMut(
+        Digit,
+        &[Set<Cell>], // the nodes of the chain in order
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    // the minilines in each house, i.e. the intersections with the lines or blocks crossing it
+    let mut house_minilines = vec![vec![]; 27];
+    for miniline in MiniLine::all() {
+        for house in House::all() {
+            if miniline.cells().without(house.cells()).is_empty() {
+                house_minilines[house.as_index()].push(miniline);
+            }
+        }
+    }
+
+    for digit in <Set<Digit>>::ALL {
+        let mut search = ChainSearch {
+            strong_links: strong_links(house_poss_positions, &house_minilines, digit, grouped),
+            chain: vec![],
+            found_ends: vec![],
+            stop_after_first,
+        };
+
+        // a chain of 2 nodes would be a single strong link, which only eliminates like locked candidates
+        for length in (4..=max_length).step_by(2) {
+            for i in 0..search.strong_links.len() {
+                let (start, next) = search.strong_links[i];
+                search.chain.extend_from_slice(&[start, next]);
+                let stop = search.extend(digit, length, &mut on_x_chain);
+                search.chain.clear();
+                if stop {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// All strong links of `digit` in both directions. Without `grouped`, the nodes are single cells.
+fn strong_links(
+    house_poss_positions: &HouseArray<DigitArray<Set<Position<House>>>>,
+    house_minilines: &[Vec<MiniLine>],
+    digit: Digit,
+    grouped: bool,
+) -> Vec<(Set<Cell>, Set<Cell>)> {
+    let mut links = vec![];
+    for house in House::all() {
+        let positions = house_poss_positions[house][digit];
+        if positions.len() < 2 {
+            continue;
+        }
+        let cells = house.cells_at(positions);
+
+        let mut nodes = cells.into_iter().map(Cell::as_set).collect::<Vec<_>>();
+        if grouped {
+            for miniline in &house_minilines[house.as_index()] {
+                let group = cells & miniline.cells();
+                if group.len() >= 2 {
+                    nodes.push(group);
+                }
+            }
+        }
+
+        for (i, &node1) in nodes.iter().enumerate() {
+            for &node2 in &nodes[i + 1..] {
+                let is_strong_link = !node1.overlaps(node2) && node1 | node2 == cells;
+                // the same link can exist in several houses
+                if is_strong_link && !links.contains(&(node1, node2)) {
+                    links.push((node1, node2));
+                    links.push((node2, node1));
+                }
+            }
+        }
+    }
+    links
+}
+
+// the cells that see every cell of `node`
+fn common_peers(node: Set<Cell>) -> Set<Cell> {
+    node.into_iter()
+        .fold(Set::ALL, |peers, cell| peers & cell.peer_set())
+}
+
+struct ChainSearch {
+    strong_links: Vec<(Set<Cell>, Set<Cell>)>,
+    chain: Vec<Set<Cell>>,
+    // end nodes of the chains reported so far
+    found_ends: Vec<(Set<Cell>, Set<Cell>)>,
+    stop_after_first: bool,
+}
+
+impl ChainSearch {
+    // Extend the chain by a weak and a strong link at a time until it has `length` nodes.
+    // Returns `true`, if the search should stop.
+    fn extend(
+        &mut self,
+        digit: Digit,
+        length: usize,
+        on_x_chain: &mut impl FnMut(Digit, &[Set<Cell>]) -> bool,
+    ) -> bool {
+        let first = self.chain[0];
+        let last = self.chain[self.chain.len() - 1];
+        let chain_cells = self.chain.iter().fold(Set::NONE, |cells, &node| cells | node);
+        let weakly_linked = common_peers(last);
+
+        for i in 0..self.strong_links.len() {
+            let (node1, node2) = self.strong_links[i];
+            if !node1.without(weakly_linked).is_empty() || (node1 | node2).overlaps(chain_cells) {
+                continue;
+            }
+            self.chain.extend_from_slice(&[node1, node2]);
+
+            let stop = if self.chain.len() < length {
+                self.extend(digit, length, on_x_chain)
+            } else if !self.found_ends.contains(&(first, node2))
+                // every chain is also found in reverse
+                && !self.found_ends.contains(&(node2, first))
+            {
+                self.found_ends.push((first, node2));
+                on_x_chain(digit, &self.chain) && self.stop_after_first
+            } else {
+                false
+            };
+
+            self.chain.truncate(self.chain.len() - 2);
+            if stop {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// the cells that see every cell of both end nodes of `chain`
+pub(crate) fn chain_conflict_cells(chain: &[Set<Cell>]) -> Set<Cell> {
+    common_peers(chain[0]) & common_peers(chain[chain.len() - 1])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy};
+
+    #[test]
+    fn x_chain() {
+        let sudoku = Sudoku::from_str_line(
+            "..62.81...2.1.........6.4.7.....951...97518...124.....9.7.1.........7.5...18.26..",
+        )
+        .unwrap();
+        // r2c6 = r7c6 - r7c8 = r5c8 - r4c9 = r4c3
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::XChain) {
+            Deduction::XChain {
+                digit,
+                cells,
+                conflicts,
+            } => {
+                assert_eq!(digit, Digit::new(4));
+                let chain = [14, 59, 61, 43, 35, 29];
+                assert_eq!(
+                    cells,
+                    chain
+                        .iter()
+                        .fold(Set::NONE, |cells, &cell| cells | Cell::new(cell))
+                );
+                assert_eq!(conflicts, [Candidate::new(11, 4)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn grouped_x_chain() {
+        let sudoku = Sudoku::from_str_line(
+            ".3.4.597.9.....3...6...3..4.....47.1.4.286.3.5.97.....4..3...9...6.....7.986.7.1.",
+        )
+        .unwrap();
+        // r2c2 = r2c9 - r7c9 = r7c2|r7c3, ending in a group of 2 cells
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::GroupedXChain) {
+            Deduction::GroupedChain {
+                digit,
+                cells,
+                ends,
+                conflicts,
+            } => {
+                assert_eq!(digit, Digit::new(5));
+                assert_eq!(cells.len(), 5);
+                assert_eq!(
+                    ends,
+                    [Cell::new(10).as_set(), Cell::new(55).as_set() | Cell::new(56)]
+                );
+                assert_eq!(conflicts, [Candidate::new(64, 5)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::prelude::*;
+
+/// Maximum number of cells in the chains that [`Strategy::XyChain`](crate::strategy::Strategy::XyChain)
+/// searches for.
+pub(crate) const MAX_LENGTH: usize = 10;
+
+// An xy-chain is a sequence of bivalue cells in which each cell sees the next one and shares a digit with it.
+// If the first cell doesn't contain `digit`, it must contain its other candidate, so the next cell can't contain
+// that one and must contain its own other candidate and so on. If the last cell is then forced to `digit`,
+// either the first or the last cell contains `digit` and it can be removed from all cells that see both.
+//
+// Chains are searched from short to long, so each pair of end cells is reported once with its shortest chain.
+pub(crate) fn find_xy_chain(
+    cells_poss_digits: &CellArray<Set<Digit>>,
+    max_length: usize,
+    stop_after_first: bool,
+    mut on_xy_chain: impl FnMut(
+        &[Cell], // the cells of the chain in order
+        Digit,   // the digit that one of the end cells must contain
+    ) -> bool,
+) -> Result<(), Unsolvable> {
+    let mut bivalue_cells = DigitArray([Set::NONE; 9]);
+    for cell in Cell::all() {
+        let digits = cells_poss_digits[cell];
+        if digits.len() == 2 {
+            for digit in digits {
+                bivalue_cells[digit] |= cell;
+            }
+        }
+    }
+
+    let mut search = ChainSearch {
+        cells_poss_digits,
+        bivalue_cells,
+        chain: vec![],
+        found_ends: vec![],
+        stop_after_first,
+    };
+
+    // a chain of 2 cells would be a naked pair
+    for length in 3..=max_length {
+        for cell in Cell::all() {
+            let digits = cells_poss_digits[cell];
+            if digits.len() != 2 {
+                continue;
+            }
+            for digit in digits {
+                let forced_digit = other_digit(digits, digit);
+                search.chain.push(cell);
+                let stop = search.extend(digit, forced_digit, length, &mut on_xy_chain);
+                search.chain.pop();
+                if stop {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+struct ChainSearch<'a> {
+    cells_poss_digits: &'a CellArray<Set<Digit>>,
+    // bivalue cells by the digits they contain
+    bivalue_cells: DigitArray<Set<Cell>>,
+    chain: Vec<Cell>,
+    // end cells and digit of the chains reported so far
+    found_ends: Vec<(Cell, Cell, Digit)>,
+    stop_after_first: bool,
+}
+
+impl ChainSearch<'_> {
+    // Extend the chain until it has `length` cells. `forced_digit` is the digit the last cell must contain,
+    // if the first one doesn't contain `digit`. Returns `true`, if the search should stop.
+    fn extend(
+        &mut self,
+        digit: Digit,
+        forced_digit: Digit,
+        length: usize,
+        on_xy_chain: &mut impl FnMut(&[Cell], Digit) -> bool,
+    ) -> bool {
+        let first = self.chain[0];
+        let last = self.chain[self.chain.len() - 1];
+        let chain_cells = self.chain.iter().fold(Set::NONE, |cells, &cell| cells | cell);
+        let next_cells = (last.peer_set() & self.bivalue_cells[forced_digit]).without(chain_cells);
+
+        for next in next_cells {
+            let next_forced_digit = other_digit(self.cells_poss_digits[next], forced_digit);
+            self.chain.push(next);
+
+            let stop = if self.chain.len() < length {
+                self.extend(digit, next_forced_digit, length, on_xy_chain)
+            } else if next_forced_digit == digit
+                // every chain is also found in reverse
+                && first < next
+                && !self.found_ends.contains(&(first, next, digit))
+            {
+                self.found_ends.push((first, next, digit));
+                on_xy_chain(&self.chain, digit) && self.stop_after_first
+            } else {
+                false
+            };
+
+            self.chain.pop();
+            if stop {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// the candidate of a bivalue cell besides `digit`
+fn other_digit(digits: Set<Digit>, digit: Digit) -> Digit {
+    digits.without(digit.as_set()).into_iter().next().unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::strategies::{first_deduction, TEST_BASE_STRATEGIES};
+    use crate::strategy::{Deduction, Strategy, StrategySolver};
+
+    #[test]
+    fn xy_chain() {
+        let sudoku = Sudoku::from_str_line(
+            "...4..259..4..861.....95..4.3......8.4.9.6.3.2......6.4..57.....723..9..563..9...",
+        )
+        .unwrap();
+        // 5 cells from r4c5 to r6c6
+        match first_deduction(sudoku, TEST_BASE_STRATEGIES, Strategy::XyChain) {
+            Deduction::XyChain {
+                cells,
+                ends,
+                digit,
+                conflicts,
+            } => {
+                assert_eq!(cells.len(), 5);
+                assert_eq!(ends, Cell::new(31).as_set() | Cell::new(50));
+                assert_eq!(digit, Digit::new(4));
+                assert_eq!(conflicts, [Candidate::new(32, 4)]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn xy_chain_eliminations_are_correct() {
+        let sudokus = include_str!("../../../sudokus/Lines/finned_xwing_sudokus.txt")
+            .lines()
+            .map(|line| Sudoku::from_str_line(line).unwrap());
+
+        // every digit a chain eliminates must differ from the one the solution has in that cell,
+        // which goes wrong if the digit entering a cell is confused with the one leaving it
+        let mut n_chains = 0;
+        for sudoku in sudokus {
+            let solution = sudoku.solution().unwrap().to_bytes();
+            let mut solver = StrategySolver::from_sudoku(sudoku);
+            solver.solve_in_place(TEST_BASE_STRATEGIES);
+
+            let deductions = solver.preview(&[Strategy::XyChain], usize::MAX);
+            for deduction in deductions.iter() {
+                if let Deduction::XyChain { conflicts, .. } = deduction {
+                    n_chains += 1;
+                    for conflict in conflicts {
+                        assert_ne!(solution[conflict.cell.as_index()], conflict.digit.get());
+                    }
+                }
+            }
+        }
+        assert!(n_chains > 0);
+    }
+}
@@ -88,4 +88,32 @@ mod test {
             panic!("No XyWing found.");
         }
     }
+
+    #[test]
+    fn xy_wing_required() {
+        use crate::strategy::{Strategy, StrategySolver};
+
+        let sudoku = Sudoku::from_str_line(
+            "7..48...6......7..8..3..5.1..1..43.9....9....9.32..4..3.9..6..2..6......2...51..3",
+        )
+        .unwrap();
+        let mut strategies = vec![
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            Strategy::NakedPairs,
+            Strategy::XWing,
+            Strategy::HiddenPairs,
+            Strategy::NakedTriples,
+            Strategy::Swordfish,
+            Strategy::HiddenTriples,
+            Strategy::NakedQuads,
+            Strategy::HiddenQuads,
+        ];
+        assert!(StrategySolver::from_sudoku(sudoku).solve(&strategies).is_err());
+
+        strategies.push(Strategy::XyWing);
+        let (solution, _) = StrategySolver::from_sudoku(sudoku).solve(&strategies).unwrap();
+        assert_eq!(Some(solution), sudoku.solution());
+    }
 }
@@ -94,4 +94,50 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn xyz_wing_required() {
+        use crate::strategy::{Strategy, StrategySolver};
+
+        let sudoku = Sudoku::from_str_line(
+            "1..5.........8..9...2.134.5..3.25..77.8...9.25..94.3..2.139.5...3..5.........4..9",
+        )
+        .unwrap();
+        let mut strategies = vec![
+            Strategy::NakedSingles,
+            Strategy::HiddenSingles,
+            Strategy::LockedCandidates,
+            Strategy::NakedPairs,
+            Strategy::XWing,
+            Strategy::HiddenPairs,
+            Strategy::NakedTriples,
+            Strategy::Swordfish,
+            Strategy::HiddenTriples,
+            Strategy::NakedQuads,
+            Strategy::HiddenQuads,
+        ];
+        assert!(StrategySolver::from_sudoku(sudoku).solve(&strategies).is_err());
+
+        strategies.push(Strategy::XyzWing);
+        let (solution, deductions) = StrategySolver::from_sudoku(sudoku).solve(&strategies).unwrap();
+        assert_eq!(Some(solution), sudoku.solution());
+
+        // the eliminated cells see the hinge and both pincers
+        for deduction in deductions.iter() {
+            if let crate::strategy::Deduction::Wing {
+                hinge,
+                pincers,
+                conflicts,
+                ..
+            } = deduction
+            {
+                for conflict in conflicts {
+                    assert!(pincers
+                        .into_iter()
+                        .chain(Some(hinge))
+                        .all(|cell| cell.peer_set().contains(conflict.cell)));
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,179 @@
+//! A facade over the [`StrategySolver`] for interactive solving
+
+use super::{Deduction, Strategy, StrategySolver};
+use crate::board::*;
+use crate::Sudoku;
+
+/// A sudoku that is being solved interactively.
+///
+/// The `Workspace` starts out with the givens of a sudoku. Candidates can be added and
+/// removed by the user, hints can be requested and applied and the resulting state of the grid
+/// can be exported at any time.
+///
+/// ```
+/// # use sudoku::Sudoku;
+/// # use sudoku::strategy::{Strategy, Workspace};
+/// let sudoku = Sudoku::from_str_line(
+///     "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+/// ).unwrap();
+/// let mut workspace = Workspace::new(sudoku);
+///
+/// let hint = workspace.hint(&[Strategy::NakedSingles, Strategy::HiddenSingles]);
+/// assert!(hint.is_some());
+/// assert!(workspace.apply_hint());
+///
+/// let grid_state = workspace.grid_state();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    givens: Sudoku,
+    solver: StrategySolver,
+    // the solver state after the last hint, if nothing changed since then
+    hint: Option<StrategySolver>,
+}
+
+impl Workspace {
+    /// Creates a new workspace from the givens of a sudoku.
+    pub fn new(givens: Sudoku) -> Workspace {
+        Workspace {
+            givens,
+            solver: StrategySolver::from_sudoku_and_clues(givens, givens),
+            hint: None,
+        }
+    }
+
+    /// Returns the givens the workspace was created from.
+    pub fn givens(&self) -> Sudoku {
+        self.givens
+    }
+
+    /// Adds `candidate` back to its cell. Returns `false` and does nothing, if the cell already
+    /// contains a digit or a cell in the same house contains the same digit.
+    pub fn add_candidate(&mut self, candidate: Candidate) -> bool {
+        let mut grid_state = self.solver.grid_state();
+        let Candidate { cell, digit } = candidate;
+        let conflicts_with_peer = cell
+            .peer_set()
+            .into_iter()
+            .any(|peer| grid_state[peer.as_index()] == CellState::Digit(digit));
+
+        match &mut grid_state[cell.as_index()] {
+            CellState::Candidates(digits) if !conflicts_with_peer => *digits |= digit,
+            _ => return false,
+        }
+
+        self.solver = StrategySolver::from_grid_state(grid_state);
+        self.solver.clues = Some(self.givens);
+        self.solver.update_grid_state_caches();
+        self.hint = None;
+        true
+    }
+
+    /// Removes `candidate` from its cell. Returns `false` and does nothing, if the cell already
+    /// contains a digit.
+    pub fn remove_candidate(&mut self, candidate: Candidate) -> bool {
+        if let CellState::Digit(_) = self.solver.cell_state(candidate.cell) {
+            return false;
+        }
+        self.solver.eliminated_entries.push(candidate);
+        self.solver.update_grid_state_caches();
+        self.hint = None;
+        true
+    }
+
+    /// Searches for a deduction using the first of the given `strategies` that finds one.
+    /// The workspace is not changed until the hint is applied with [`apply_hint`](Self::apply_hint).
+    ///
+    /// Returns `None`, if none of the strategies find anything.
+    pub fn hint(&mut self, strategies: &[Strategy]) -> Option<Deduction<&[Candidate]>> {
+        let mut solver = self.solver.clone();
+        let n_deductions = solver.deductions.len();
+        self.hint = match solver.apply_one_step(strategies) {
+            true => {
+                solver.update_grid_state_caches();
+                Some(solver)
+            }
+            false => None,
+        };
+
+        let solver = self.hint.as_ref()?;
+        let deduction = solver.deductions[n_deductions].clone();
+        Some(deduction.with_slices(&solver.eliminated_entries))
+    }
+
+    /// Applies the last hint found by [`hint`](Self::hint). Returns `false` and does nothing,
+    /// if there is no hint or the workspace has changed since the hint was found.
+    pub fn apply_hint(&mut self) -> bool {
+        match self.hint.take() {
+            Some(solver) => {
+                self.solver = solver;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the current state of all cells, either a digit or the remaining candidates.
+    pub fn grid_state(&self) -> [CellState; 81] {
+        self.solver.grid_state()
+    }
+
+    /// Returns the digits entered so far, including the givens.
+    pub fn to_sudoku(&self) -> Sudoku {
+        let mut sudoku = Sudoku([0; 81]);
+        for (digit, cell_state) in sudoku.0.iter_mut().zip(self.grid_state().iter()) {
+            if let CellState::Digit(cell_digit) = cell_state {
+                *digit = cell_digit.get();
+            }
+        }
+        sudoku
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn workspace() {
+        let sudoku = Sudoku::from_str_line(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        let mut workspace = Workspace::new(sudoku);
+        assert_eq!(workspace.to_sudoku(), sudoku);
+        let cell = Cell::new(0);
+        let candidates = |workspace: &Workspace| match workspace.grid_state()[0] {
+            CellState::Candidates(digits) => digits,
+            CellState::Digit(_) => panic!("cell 0 is not empty"),
+        };
+        let all_candidates = candidates(&workspace);
+
+        // 3 is already in the same row
+        assert!(!workspace.add_candidate(Candidate::new(0, 3)));
+        // 2 is in cell 4
+        assert!(!workspace.remove_candidate(Candidate::new(4, 2)));
+
+        let solution = sudoku.solution().unwrap();
+        let digit = all_candidates
+            .into_iter()
+            .find(|digit| digit.get() != solution.0[0])
+            .unwrap();
+        assert!(workspace.remove_candidate(Candidate { cell, digit }));
+        assert_eq!(candidates(&workspace), all_candidates.without(digit.as_set()));
+        assert!(workspace.add_candidate(Candidate { cell, digit }));
+        assert_eq!(candidates(&workspace), all_candidates);
+
+        // hints are discarded when the workspace changes
+        let strategies = &[Strategy::NakedSingles, Strategy::HiddenSingles];
+        assert!(workspace.hint(strategies).is_some());
+        assert!(workspace.remove_candidate(Candidate { cell, digit }));
+        assert!(!workspace.apply_hint());
+
+        while workspace.hint(strategies).is_some() {
+            assert!(workspace.apply_hint());
+        }
+        assert_eq!(workspace.to_sudoku(), solution);
+        assert_eq!(workspace.givens(), sudoku);
+    }
+}
@@ -1,4 +1,14 @@
-use sudoku::Sudoku;
+use quickcheck::quickcheck;
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    str,
+    time::{Duration, Instant},
+};
+use sudoku::bitset::Set;
+use sudoku::board::Cell;
+use sudoku::parse_errors::{BlockParseError, FromBytesError, InvalidEntry, LineParseError, ParseError};
+use sudoku::{GridError, SolveStats, Sudoku, Timeout, Uniqueness};
 
 fn read_sudokus(sudokus_str: &str) -> Vec<Sudoku> {
     sudokus_str
@@ -92,6 +102,255 @@ fn from_str_line_with_comment() {
     }
 }
 
+#[test]
+fn from_str_line_errors() {
+    let line = "...2...633....54.1..1..398........9....538....3........263..5..5.37....847...1...";
+    let with_char_at = |idx: usize, ch: char| {
+        let mut chars: Vec<char> = line.chars().collect();
+        chars[idx] = ch;
+        chars.into_iter().collect::<String>()
+    };
+
+    assert_eq!(
+        Sudoku::from_str_line(&with_char_at(40, 'x')),
+        Err(LineParseError::InvalidEntry(InvalidEntry { cell: 40, ch: 'x' }))
+    );
+    assert_eq!(
+        Sudoku::from_str_line(&with_char_at(5, 'ä')),
+        Err(LineParseError::InvalidEntry(InvalidEntry { cell: 5, ch: 'ä' }))
+    );
+    assert_eq!(
+        Sudoku::from_str_line(&line[..80]),
+        Err(LineParseError::NotEnoughCells(80))
+    );
+    for &delimiter in &[' ', '\t', '\r', '\n', ';', ','] {
+        assert_eq!(
+            Sudoku::from_str_line(&with_char_at(20, delimiter)),
+            Err(LineParseError::NotEnoughCells(20))
+        );
+    }
+    assert_eq!(
+        Sudoku::from_str_line(&format!("{}1", line)),
+        Err(LineParseError::TooManyCells)
+    );
+    assert_eq!(
+        Sudoku::from_str_line(&format!("{}x", line)),
+        Err(LineParseError::MissingCommentDelimiter)
+    );
+}
+
+#[test]
+fn parse_error_messages() {
+    let block = "\
+___|2__|_63
+3__|__5|4_1
+__1|__3|98_
+---+---+---
+___|___|_9_
+___|538|___
+_3_|___|___
+---+---+---
+_26|3__|5__
+5_3|7/_|__8
+47_|__1|___";
+    let err = Sudoku::from_str_block(block).unwrap_err();
+    assert_eq!(err.to_string(), "row 8: invalid character '/' in column 5");
+
+    let read = |line: &str| -> Result<Sudoku, String> { Ok(Sudoku::from_str_line(line)?) };
+    assert_eq!(
+        read("123"),
+        Err(String::from("sudoku contains 3 cells instead of required 81"))
+    );
+    let err: Box<dyn std::error::Error> = Box::new(Sudoku::from_str_line("123").unwrap_err());
+    assert_eq!(err.to_string(), "sudoku contains 3 cells instead of required 81");
+}
+
+#[test]
+fn from_bytes_errors() {
+    let sudoku = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"))[0];
+    let mut bytes = sudoku.to_bytes();
+    assert_eq!(Sudoku::from_bytes(bytes), Ok(sudoku));
+
+    bytes[40] = 10;
+    bytes[50] = 255;
+    assert_eq!(
+        Sudoku::from_bytes(bytes),
+        Err(FromBytesError::InvalidNumber { cell: 40, number: 10 })
+    );
+    assert_eq!(Sudoku::from_bytes_slice(&bytes), Sudoku::from_bytes(bytes));
+    assert_eq!(Sudoku::try_from(bytes), Sudoku::from_bytes(bytes));
+    assert_eq!(
+        Sudoku::from_bytes_slice(&bytes[..80]),
+        Err(FromBytesError::WrongLength(80))
+    );
+    assert_eq!(
+        Sudoku::from_bytes(bytes).unwrap_err().to_string(),
+        "cell 40 contains 10, which is not from 0 to 9"
+    );
+}
+
+#[test]
+fn parse_detect_format() {
+    let line = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    let sudoku = Sudoku::from_str_line(line).unwrap();
+    assert_eq!(line.parse(), Ok(sudoku));
+    assert_eq!(line.replace('.', "0").parse(), Ok(sudoku));
+    assert_eq!(format!("\n{}\n", line.replace('.', "_")).parse(), Ok(sudoku));
+    let block = line
+        .as_bytes()
+        .chunks(9)
+        .map(|row| std::str::from_utf8(row).unwrap().to_string() + "\n")
+        .collect::<String>();
+    assert_eq!(block.parse(), Ok(sudoku));
+
+    assert_eq!(
+        line[..80].parse::<Sudoku>(),
+        Err(ParseError::Line(LineParseError::NotEnoughCells(80)))
+    );
+    assert_eq!(
+        line[..16].parse::<Sudoku>(),
+        Err(ParseError::Line(LineParseError::NotEnoughCells(16)))
+    );
+    assert_eq!(
+        line[..9].parse::<Sudoku>(),
+        Err(ParseError::Block(BlockParseError::NotEnoughRows(1)))
+    );
+    let three_rows = format!("{}\n{}\n{}\n", &line[..9], &line[9..18], &line[18..27]);
+    let err = Sudoku::from_str_block(&three_rows).unwrap_err();
+    assert_eq!(err, BlockParseError::NotEnoughRows(3));
+    assert_eq!(err.to_string(), "sudoku contains 3 rows instead of required 9");
+
+    let input = format!("{}\n\n{}{}\n", line, block, &line[..80]);
+    let mut reader = input.as_bytes();
+    assert_eq!(Sudoku::from_reader(&mut reader).unwrap(), Some(Ok(sudoku)));
+    assert_eq!(Sudoku::from_reader(&mut reader).unwrap(), Some(Ok(sudoku)));
+    assert_eq!(
+        Sudoku::from_reader(&mut reader).unwrap(),
+        Some(Err(ParseError::Line(LineParseError::NotEnoughCells(80))))
+    );
+    assert_eq!(Sudoku::from_reader(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn empty_cell_characters() {
+    let underscores = "\
+___|2__|_63
+3__|__5|4_1
+__1|__3|98_
+---+---+---
+___|___|_9_
+___|538|___
+_3_|___|___
+---+---+---
+_26|3__|5__
+5__|___|__8
+47_|__1|___";
+    let sudoku = Sudoku::from_str_block(underscores).unwrap();
+    for &blank in &[".", "0"] {
+        let block = underscores.replace('_', blank);
+        assert_eq!(Sudoku::from_str_block(&block), Ok(sudoku));
+        assert_eq!(Sudoku::from_str_block_permissive(&block), Ok(sudoku));
+        assert_eq!(
+            Sudoku::from_reader_block(&mut block.as_bytes()).unwrap(),
+            Some(Ok(sudoku))
+        );
+        assert_eq!(block.parse(), Ok(sudoku));
+    }
+
+    // mixed within one sudoku
+    let line = sudoku.to_str_line().replacen('.', "0", 20).replacen('.', "_", 20);
+    assert_eq!(Sudoku::from_str_line(&line), Ok(sudoku));
+    assert_eq!(line.parse(), Ok(sudoku));
+}
+
+#[test]
+fn is_valid() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for sudoku in sudokus {
+        assert!(sudoku.is_valid());
+        assert!(sudoku.solution().unwrap().is_valid());
+    }
+    assert!(Sudoku::empty().is_valid());
+
+    // two 5s in the first row
+    let mut bytes = [0; 81];
+    bytes[2] = 5;
+    bytes[7] = 5;
+    assert!(!Sudoku::from_bytes(bytes).unwrap().is_valid());
+
+    // consistent, but unsolvable
+    let sudoku = Sudoku::from_str_line(
+        "...4..259..4..861.....95..4.3......8.4.9.6.7.2......6.4..57.....723..9..563..9...",
+    )
+    .unwrap();
+    assert_eq!(sudoku.validate(), Err(GridError::NoSolution));
+    assert!(sudoku.is_valid());
+}
+
+#[test]
+fn array_2d() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for sudoku in sudokus {
+        let grid = sudoku.to_2d();
+        for (row, row_digits) in sudoku.rows().enumerate() {
+            for col in 0..9 {
+                assert_eq!(grid[row][col], row_digits[col].unwrap_or(0));
+            }
+        }
+        assert_eq!(Sudoku::from_2d(grid), Ok(sudoku));
+    }
+
+    let mut grid = [[0; 9]; 9];
+    grid[8][0] = 10;
+    assert_eq!(
+        Sudoku::from_2d(grid),
+        Err(FromBytesError::InvalidNumber { cell: 72, number: 10 })
+    );
+}
+
+#[test]
+fn line_roundtrip() {
+    for line in include_str!("../sudokus/Lines/easy_sudokus.txt").lines() {
+        let line = &line[..81];
+        let sudoku = Sudoku::from_str_line(line).unwrap();
+        // canonical form with '.' for empty cells
+        let canonical = sudoku.to_str_line();
+        assert_eq!(canonical.len(), 81);
+        assert_eq!(*canonical, line.replace(&['_', '0'][..], "."));
+        assert_eq!(
+            Sudoku::from_str_line(&canonical).unwrap().to_str_line(),
+            canonical
+        );
+    }
+}
+
+#[test]
+fn checksum() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for sudoku in sudokus {
+        let line = sudoku.to_str_line_checked();
+        assert_eq!(Sudoku::from_str_line_checked(&line), Ok(sudoku));
+        assert_eq!(Sudoku::from_str_line(&line), Ok(sudoku));
+
+        let with_comment = format!("{};comment {}\n", sudoku.to_str_line(), &line[82..]);
+        assert_eq!(Sudoku::from_str_line_checked(&with_comment), Ok(sudoku));
+
+        // corrupt a single cell
+        let mut bytes = line.into_bytes();
+        bytes[40] = if bytes[40] == b'.' { b'1' } else { b'.' };
+        let corrupted = str::from_utf8(&bytes).unwrap();
+        assert_eq!(
+            Sudoku::from_str_line_checked(corrupted),
+            Err(LineParseError::ChecksumMismatch)
+        );
+
+        assert_eq!(
+            Sudoku::from_str_line_checked(&sudoku.to_str_line()),
+            Err(LineParseError::ChecksumMismatch)
+        );
+    }
+}
+
 #[test]
 #[should_panic]
 fn wrong_format_1() {
@@ -115,6 +374,215 @@ fn solutionless_sudokus() {
     }
 }
 
+#[test]
+fn solution_with_stats() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    let solved_sudokus = read_sudokus(include_str!("../sudokus/Lines/solved_hard_sudokus.txt"));
+    let mut total_guesses = 0;
+    for (sudoku, solved_sudoku) in sudokus.into_iter().zip(solved_sudokus) {
+        let (solution, stats) = sudoku.possibly_nonunique_solution_with_stats().unwrap();
+        assert_eq!(solution, solved_sudoku);
+        assert!(stats.max_depth <= stats.guesses);
+        total_guesses += stats.guesses;
+    }
+    assert!(total_guesses > 0);
+
+    let solved_sudoku = read_sudokus(include_str!("../sudokus/Lines/solved_easy_sudokus.txt"))[0];
+    let (_, stats) = solved_sudoku.possibly_nonunique_solution_with_stats().unwrap();
+    assert_eq!(stats, SolveStats::default());
+
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/invalid_sudokus.txt")) {
+        assert!(sudoku.possibly_nonunique_solution_with_stats().is_none());
+    }
+}
+
+#[test]
+fn is_proper() {
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt")) {
+        assert!(sudoku.is_proper());
+    }
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/invalid_sudokus.txt")) {
+        assert!(!sudoku.is_proper());
+    }
+    assert!(!Sudoku::from_bytes([0; 81]).unwrap().is_proper());
+}
+
+#[test]
+fn uniqueness() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let solved_sudokus = read_sudokus(include_str!("../sudokus/Lines/solved_easy_sudokus.txt"));
+    for (sudoku, solution) in sudokus.into_iter().zip(solved_sudokus).take(20) {
+        assert_eq!(sudoku.uniqueness(), Uniqueness::Unique(solution));
+    }
+
+    match Sudoku::from_bytes([0; 81]).unwrap().uniqueness() {
+        Uniqueness::Multiple(first, second) => {
+            assert!(first.is_solved() && second.is_solved());
+            assert_ne!(first, second);
+        }
+        other => panic!("expected multiple solutions, got {:?}", other),
+    }
+
+    let mut conflict = [0; 81];
+    conflict[0] = 1;
+    conflict[1] = 1;
+    assert_eq!(
+        Sudoku::from_bytes(conflict).unwrap().uniqueness(),
+        Uniqueness::NoSolution
+    );
+}
+
+#[test]
+fn validate() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(20) {
+        assert_eq!(sudoku.validate(), Ok(()));
+    }
+
+    // the first row needs a 9 in the last cell, but the block already contains one
+    let sudoku = Sudoku::from_str_line(
+        "12345678.........9...............................................................",
+    )
+    .unwrap();
+    assert_eq!(sudoku.validate(), Err(GridError::NoSolution));
+
+    let mut conflict = [0; 81];
+    conflict[0] = 1;
+    conflict[10] = 1;
+    let expected_cells = [Cell::new(0), Cell::new(10)]
+        .iter()
+        .fold(Set::NONE, |set, &cell| set | cell);
+    assert_eq!(
+        Sudoku::from_bytes(conflict).unwrap().validate(),
+        Err(GridError::DuplicateInHouse {
+            cells: expected_cells
+        })
+    );
+}
+
+#[test]
+fn solution_with_deadline() {
+    let far_future = Instant::now() + Duration::from_secs(3600);
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/hard_sudokus.txt"));
+    for sudoku in sudokus.into_iter().take(20) {
+        assert_eq!(
+            sudoku.possibly_nonunique_solution_with_deadline(far_future),
+            Ok(sudoku.possibly_nonunique_solution())
+        );
+    }
+
+    // the empty sudoku can only be solved by guessing
+    let past = Instant::now();
+    assert_eq!(
+        Sudoku::empty().possibly_nonunique_solution_with_deadline(past),
+        Err(Timeout)
+    );
+}
+
+#[test]
+fn empty_grid() {
+    let empty = Sudoku::empty();
+    let start = Instant::now();
+
+    assert_eq!(empty.solutions_count_up_to(2), 2);
+    assert!(!empty.is_uniquely_solvable());
+    assert_eq!(empty.solution(), None);
+
+    let solutions = empty.solutions_up_to(10);
+    assert_eq!(solutions.len(), 10);
+    for (i, solution) in solutions.iter().enumerate() {
+        assert!(solution.is_solved());
+        assert!(!solutions[..i].contains(solution));
+    }
+    // the search stops at the limit, so this takes well below a millisecond
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // there is nothing to remove and no unique solution to keep
+    assert_eq!(Sudoku::generate_from(empty), empty);
+    let generated = Sudoku::generate();
+    assert!(generated.is_uniquely_solvable());
+    assert!(generated.n_clues() >= 17);
+}
+
+#[test]
+fn distinct_solutions() {
+    let solution = Sudoku::from_str_line(
+        "975436218346812975281597436824371659193265784567948123632154897458729361719683542",
+    )
+    .unwrap();
+    for n_cleared in &[20, 36, 54] {
+        let mut bytes = solution.to_bytes();
+        for cell in bytes[..*n_cleared].iter_mut() {
+            *cell = 0;
+        }
+        let sudoku = Sudoku::from_bytes(bytes).unwrap();
+
+        let solutions = sudoku.solutions_up_to(300);
+        let distinct = solutions.iter().collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), solutions.len());
+        assert!(solutions.iter().all(Sudoku::is_solved));
+        assert_eq!(sudoku.solutions_count_up_to(300), solutions.len());
+
+        let mut buffer = vec![[0; 81]; 300];
+        let n_solutions = sudoku.solutions_up_to_buffer(&mut buffer, 300);
+        assert_eq!(n_solutions, solutions.len());
+        assert!(buffer[..n_solutions]
+            .iter()
+            .zip(&solutions)
+            .all(|(bytes, solution)| bytes == &solution.to_bytes()));
+    }
+}
+
+#[test]
+fn best_effort_solution() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    for &sudoku in &sudokus[..10] {
+        assert_eq!(Some(sudoku.best_effort_solution()), sudoku.solution());
+    }
+
+    // a proper sudoku with one clue changed, so no clues conflict but there is no solution
+    let unsolvable = Sudoku::from_str_line(
+        "...4..259..4..861.....95..4.3......8.4.9.6.7.2......6.4..57.....723..9..563..9...",
+    )
+    .unwrap();
+    assert_eq!(unsolvable.validate(), Err(GridError::NoSolution));
+    let partial = unsolvable.best_effort_solution();
+    assert!(!partial.is_solved());
+    assert!(partial.n_clues() > unsolvable.n_clues());
+    assert_eq!(partial.validate(), Err(GridError::NoSolution));
+    for (clue, entry) in unsolvable.iter().zip(partial.iter()) {
+        assert!(clue.is_none() || clue == entry);
+    }
+
+    // conflicting clues are returned unchanged
+    let conflicting = Sudoku::from_str_line(
+        "11...............................................................................",
+    )
+    .unwrap();
+    assert_eq!(conflicting.best_effort_solution(), conflicting);
+}
+
+#[test]
+fn solution_without_guessing() {
+    let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
+    let solved_sudokus = read_sudokus(include_str!("../sudokus/Lines/solved_easy_sudokus.txt"));
+    let mut n_solved = 0;
+    for (sudoku, solution) in sudokus.iter().zip(&solved_sudokus) {
+        if let Some(solved) = sudoku.solution_without_guessing() {
+            assert_eq!(&solved, solution);
+            n_solved += 1;
+        }
+    }
+    assert!(n_solved > sudokus.len() / 2);
+    assert_eq!(
+        Sudoku::from_bytes([0; 81]).unwrap().solution_without_guessing(),
+        None
+    );
+    for sudoku in read_sudokus(include_str!("../sudokus/Lines/invalid_sudokus.txt")) {
+        assert_eq!(sudoku.solution_without_guessing(), None);
+    }
+}
+
 #[test]
 fn is_solved_on_unsolved() {
     let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
@@ -140,6 +608,24 @@ fn solution_multiple_solutions() {
     sudoku.solution().unwrap();
 }
 
+// Regression test for the order in which solutions are found.
+// Changes to the solver may change the order, but it must never be random.
+#[test]
+fn solution_order_is_deterministic() {
+    let empty = Sudoku::from_bytes([0; 81]).unwrap();
+    let first_solutions = [
+        "123456789456789123789123456231547698567891234894362517312974865645238971978615342",
+        "123456789456789123789123456231547698567891234894362517312974865648235971975618342",
+        "123456789456789123789123456231547698567891234894362517312974865675238941948615372",
+    ];
+
+    let solutions = empty.solutions_up_to(3);
+    for (solution, &expected) in solutions.iter().zip(first_solutions.iter()) {
+        assert_eq!(solution.to_str_line().to_string(), expected);
+    }
+    assert_eq!(empty.possibly_nonunique_solution(), Some(solutions[0]));
+}
+
 #[test]
 fn correct_solution_easy_sudokus() {
     let sudokus = read_sudokus(include_str!("../sudokus/Lines/easy_sudokus.txt"));
@@ -331,6 +817,22 @@ fn parse_permissive() {
         5 . . |2 . . |. . .
         1 . 4 |. . . |. . .
         ",
+        // fully bordered with distinct separators between bands
+        r"
+        *=======*=======*=======*
+        | . . 3 | . 2 . | 6 . . |
+        | 9 . . | 3 . 5 | . . 1 |
+        | . . 1 | 8 . 6 | 4 . . |
+        |=======+=======+=======|
+        | . . 8 | 1 . 2 | 9 . . |
+        | 7 . . | . . . | . . 8 |
+        | . . 6 | 7 . 8 | 2 . . |
+        |-------+-------+-------|
+        | . . 2 | 6 . 9 | 5 . . |
+        | 8 . . | 2 . 3 | . . 9 |
+        | . . 5 | . 1 . | 3 . . |
+        *=======*=======*=======*
+        ",
         // https://projecteuler.net/problem=96
         r"
         Grid 01
@@ -358,6 +860,7 @@ fn parse_permissive() {
     let sudokus_line = [
         "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
         "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
     ];
 
     for (sudoku, line_sudoku) in sudokus.iter().zip(sudokus_line.iter()) {
@@ -404,3 +907,96 @@ fn print_line() {
     let dereffed_line: &str = &line;
     println!("{}", line);
 }
+
+#[test]
+fn parse_reader_block() {
+    let input = "\n\n__3_2_6__\n9__3_5__1\n__18_64__\n__81_29__\n7_______8\n__67_82__\n__26_95__\n8__2_3__9\n__5_1_3__\n\
+                 __3|_2_|6__\n9__|3_5|__1\n__1|8_6|4__\n---+---+---\n__8|1_2|9__\n7__|___|__8\n__6|7_8|2__\n\
+                 ---+---+---\n__2|6_9|5__\n8__|2_3|__9\n__5|_1_|3__\n\n__3_2_6__\n9__3_5__1\n";
+    let mut reader = input.as_bytes();
+    let first = Sudoku::from_reader_block(&mut reader).unwrap().unwrap().unwrap();
+    let second = Sudoku::from_reader_block(&mut reader).unwrap().unwrap().unwrap();
+    assert_eq!(first, second);
+    match Sudoku::from_reader_block(&mut reader).unwrap() {
+        Some(Err(BlockParseError::NotEnoughRows(_))) => {}
+        other => panic!("expected truncated sudoku, got {:?}", other),
+    }
+    assert_eq!(Sudoku::from_reader_block(&mut reader).unwrap(), None);
+}
+
+// Blanks the cells of a random solved sudoku where `mask` is set.
+// quickcheck supplies the mask, the generator the digits.
+fn sudoku_from_mask(mask: Vec<bool>) -> Sudoku {
+    let mut bytes = Sudoku::generate_solved().to_bytes();
+    for (byte, &blank) in bytes.iter_mut().zip(mask.iter().cycle()) {
+        if blank {
+            *byte = 0;
+        }
+    }
+    Sudoku::from_bytes(bytes).unwrap()
+}
+
+quickcheck! {
+    fn roundtrip_line(mask: Vec<bool>) -> bool {
+        let sudoku = sudoku_from_mask(mask);
+        Sudoku::from_str_line(&sudoku.to_str_line()) == Ok(sudoku)
+    }
+
+    // also holds for grids that aren't valid sudokus
+    fn roundtrip_line_any_grid(bytes: Vec<u8>) -> bool {
+        let mut grid = [0; 81];
+        for (cell, &byte) in grid.iter_mut().zip(bytes.iter().cycle()) {
+            *cell = byte % 10;
+        }
+        let sudoku = Sudoku::from_bytes(grid).unwrap();
+        Sudoku::from_str_line(&sudoku.to_str_line()) == Ok(sudoku)
+    }
+
+    fn roundtrip_block(mask: Vec<bool>) -> bool {
+        let sudoku = sudoku_from_mask(mask);
+        // `display_block` groups the cells with spaces and blank lines, which only the permissive parser accepts
+        let block = sudoku.display_block().to_string();
+        let line = sudoku.to_str_line();
+        let bare_block = line.as_bytes().chunks(9).map(|row| format!("{}\n", str::from_utf8(row).unwrap())).collect::<String>();
+        Sudoku::from_str_block_permissive(&block) == Ok(sudoku)
+            && Sudoku::from_str_block(&bare_block) == Ok(sudoku)
+            && Sudoku::from_reader_block(&mut bare_block.as_bytes()).unwrap() == Some(Ok(sudoku))
+    }
+
+    fn roundtrip_bytes(mask: Vec<bool>) -> bool {
+        let sudoku = sudoku_from_mask(mask);
+        Sudoku::from_bytes(sudoku.to_bytes()) == Ok(sudoku)
+            && Sudoku::from_bytes_slice(&sudoku.to_bytes()) == Ok(sudoku)
+            && Sudoku::try_from(<[u8; 81]>::from(sudoku)) == Ok(sudoku)
+    }
+}
+
+#[cfg(feature = "serde")]
+quickcheck! {
+    fn roundtrip_serde(mask: Vec<bool>) -> bool {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let sudoku = sudoku_from_mask(mask);
+        // tokens must be 'static
+        let line = Box::leak(sudoku.to_str_line().to_string().into_boxed_str());
+        let bytes = Box::leak(Box::new(sudoku.to_bytes()));
+        assert_tokens(&sudoku.readable(), &[Token::Str(line)]);
+        assert_tokens(&sudoku.compact(), &[Token::Bytes(bytes)]);
+        true
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_errors() {
+    use serde_test::{assert_de_tokens_error, Compact, Readable, Token};
+
+    assert_de_tokens_error::<Readable<Sudoku>>(
+        &[Token::Str("123")],
+        "sudoku contains 3 cells instead of required 81",
+    );
+    assert_de_tokens_error::<Compact<Sudoku>>(
+        &[Token::Bytes(&[10; 81])],
+        "cell 0 contains 10, which is not from 0 to 9",
+    );
+}